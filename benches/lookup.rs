@@ -0,0 +1,43 @@
+//Поиск дня по дате и подсчет рабочих дней не должны зависеть
+//ни от длины календаря, ни от положения дат в нем
+use chrono::NaiveDate;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use product_calendar::pc::get_product_calendar_range;
+use product_calendar::source::default_source;
+
+fn info_by_date(c: &mut Criterion) {
+    let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let mut group = c.benchmark_group("info_by_date");
+
+    for end_year in [2022, 2024, 2026] {
+        let end = NaiveDate::from_ymd_opt(end_year, 12, 31).unwrap();
+        let pc = get_product_calendar_range(start, end, default_source()).unwrap();
+
+        for (position, date) in [("first", start), ("last", end)] {
+            group.bench_with_input(
+                BenchmarkId::new(position, pc.total_days()),
+                &date,
+                |b, &date| b.iter(|| pc.info_by_date(black_box(date))),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn work_days_between(c: &mut Criterion) {
+    let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    let mut group = c.benchmark_group("work_days_between");
+
+    for end_year in [2022, 2024, 2026] {
+        let end = NaiveDate::from_ymd_opt(end_year, 12, 31).unwrap();
+        let pc = get_product_calendar_range(start, end, default_source()).unwrap();
+
+        group.bench_function(BenchmarkId::from_parameter(pc.total_days()), |b| {
+            b.iter(|| pc.work_days_between(black_box(start), black_box(end)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, info_by_date, work_days_between);
+criterion_main!(benches);
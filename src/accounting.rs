@@ -0,0 +1,153 @@
+use crate::errors::ProductCalendarError;
+use crate::pc::ProductCalendar;
+use crate::period::{Grouping, PeriodKey};
+use crate::statistic::WeekLength;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Итог учетного периода при суммированном учете рабочего времени (ст. 104 ТК РФ).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodBalance {
+    pub period: PeriodKey,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub worked_hours: f64,
+    /// Норма рабочего времени за период по производственному календарю.
+    pub norm_hours: f64,
+}
+
+impl PeriodBalance {
+    /// Отклонение от нормы: переработка при положительном значении, недоработка - при отрицательном.
+    pub fn balance(&self) -> f64 {
+        self.worked_hours - self.norm_hours
+    }
+
+    /// Сверхурочная работа за учетный период (ст. 99 ТК РФ).
+    pub fn overtime(&self) -> f64 {
+        self.balance().max(0.0)
+    }
+
+    pub fn undertime(&self) -> f64 {
+        (-self.balance()).max(0.0)
+    }
+}
+
+/// Сравнивает отработанное время с нормой по учетным периодам `period`
+/// (месяц, квартал, год и т.д.) для рабочей недели `week`.
+///
+/// * `worked` - отработанные часы по датам, часы за одну дату суммируются;
+/// * все даты должны входить в календарь, периоды без отработанных часов
+///   тоже попадают в результат;
+/// * крайние периоды календаря могут быть неполными, норма для них считается
+///   только по дням календаря.
+pub fn summarize(
+    pc: &ProductCalendar,
+    worked: impl IntoIterator<Item = (NaiveDate, f64)>,
+    period: Grouping,
+    week: WeekLength,
+) -> Result<Vec<PeriodBalance>, ProductCalendarError> {
+    //Считаем в минутах, чтобы не копить ошибку округления
+    let mut minutes: HashMap<NaiveDate, i64> = HashMap::new();
+    for (date, hours) in worked {
+        if !pc.contains(date) {
+            return Err(ProductCalendarError::DateOutOfRange(date.to_string()));
+        }
+        let total = minutes.entry(date).or_default();
+        *total += (hours * 60.0).round() as i64;
+        if hours.is_nan() || hours < 0.0 || *total > 24 * 60 {
+            return Err(ProductCalendarError::InvalidWorkedHours(
+                date.to_string(),
+                *total as f64 / 60.0,
+            ));
+        }
+    }
+
+    Ok(pc
+        .group_by(period)
+        .into_iter()
+        .filter_map(|(key, (days, statistic))| {
+            let worked: i64 = days
+                .iter()
+                .filter_map(|day| minutes.get(&day.get_date()))
+                .sum();
+            Some(PeriodBalance {
+                period: key,
+                start: days.first()?.get_date(),
+                end: days.last()?.get_date(),
+                worked_hours: worked as f64 / 60.0,
+                norm_hours: statistic.work_hours_norm(week),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pc::get_product_calendar;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_summarize() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        //Сутки через трое в первом квартале
+        let worked = date(2024, 1, 1)
+            .iter_days()
+            .step_by(4)
+            .take_while(|d| *d <= date(2024, 3, 31))
+            .map(|d| (d, 22.5));
+
+        let quarters = summarize(&pc, worked, Grouping::Quarter, WeekLength::Forty).unwrap();
+        assert_eq!(quarters.len(), 4);
+
+        let first = &quarters[0];
+        assert_eq!(first.period.to_string(), "2024-Q1");
+        assert_eq!(
+            (first.start, first.end),
+            (date(2024, 1, 1), date(2024, 3, 31))
+        );
+        assert_eq!(first.worked_hours, 23.0 * 22.5);
+        assert_eq!(first.norm_hours, 454.0);
+        assert_eq!(first.overtime(), 63.5);
+        assert_eq!(first.undertime(), 0.0);
+
+        assert_eq!(quarters[1].worked_hours, 0.0);
+        assert_eq!(quarters[1].undertime(), quarters[1].norm_hours);
+    }
+
+    #[test]
+    fn test_invalid_hours() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        let day = date(2024, 5, 6);
+
+        let months = summarize(
+            &pc,
+            [(day, 8.0), (day, 4.5)],
+            Grouping::Month,
+            WeekLength::ThirtySix,
+        )
+        .unwrap();
+        assert_eq!(months[4].worked_hours, 12.5);
+
+        let err = summarize(
+            &pc,
+            [(day, 16.0), (day, 9.0)],
+            Grouping::Month,
+            WeekLength::Forty,
+        );
+        assert!(matches!(
+            err,
+            Err(ProductCalendarError::InvalidWorkedHours(_, _))
+        ));
+        let err = summarize(
+            &pc,
+            [(date(2025, 1, 9), 8.0)],
+            Grouping::Month,
+            WeekLength::Forty,
+        );
+        assert!(matches!(err, Err(ProductCalendarError::DateOutOfRange(_))));
+    }
+}
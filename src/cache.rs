@@ -0,0 +1,492 @@
+use crate::day::Day;
+use crate::errors::ProductCalendarError;
+use crate::norms::Norms;
+use crate::pc::ProductCalendar;
+use crate::source::CalendarSource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+lazy_static! {
+    static ref CACHE_CONFIG: RwLock<CacheConfig> = RwLock::new(CacheConfig::default());
+    //Ключ - (имя источника, год)
+    static ref CACHED_CALENDAR: Mutex<HashMap<(String, u16), CachedCalendar>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Настройки кэша календарей.
+///
+/// * `dir` - каталог для хранения календарей на диске, `None` - только в памяти процесса.
+/// * `ttl` - время жизни записи, после которого календарь загружается из источника заново.
+///   `None` - записи не устаревают.
+#[derive(Debug, Clone, Default)]
+pub struct CacheConfig {
+    pub dir: Option<PathBuf>,
+    pub ttl: Option<Duration>,
+}
+
+//Предварительный календарь в памяти периодически загружается заново,
+//чтобы долго работающий процесс получил опубликованный официальный календарь
+const PREDICTED_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone)]
+struct CachedCalendar {
+    fetched_at: u64,
+    //Общий для всех обращений, чтобы попадание в кэш не копировало дни года
+    calendar: Arc<ProductCalendar>,
+    //`None` - нормы еще не запрашивались, `Some(None)` - источник их не публикует
+    norms: Option<Option<Norms>>,
+}
+
+impl CachedCalendar {
+    fn ttl(&self, config: &CacheConfig) -> Option<Duration> {
+        if self.calendar.is_predicted() {
+            Some(
+                config
+                    .ttl
+                    .map_or(PREDICTED_TTL, |ttl| ttl.min(PREDICTED_TTL)),
+            )
+        } else {
+            config.ttl
+        }
+    }
+
+    fn is_fresh(&self, config: &CacheConfig, now: u64) -> bool {
+        match self.ttl(config) {
+            Some(ttl) => now.saturating_sub(self.fetched_at) < ttl.as_secs(),
+            None => true,
+        }
+    }
+}
+
+//Запись на диске - данные источника за год и время их получения
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    fetched_at: u64,
+    days: Vec<Day>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    norms: Option<Norms>,
+}
+
+pub fn configure(config: CacheConfig) {
+    *CACHE_CONFIG.write().unwrap() = config;
+}
+
+pub fn config() -> CacheConfig {
+    CACHE_CONFIG.read().unwrap().clone()
+}
+
+/// Удаляет календарь за год из памяти и с диска для всех источников.
+pub fn invalidate(year: u16) -> Result<(), ProductCalendarError> {
+    CACHED_CALENDAR
+        .lock()
+        .unwrap()
+        .retain(|(_, cached_year), _| *cached_year != year);
+
+    match config().dir {
+        Some(dir) => remove_year(&dir, year),
+        None => Ok(()),
+    }
+}
+
+/// Полностью очищает кэш в памяти и на диске.
+pub fn clear() -> Result<(), ProductCalendarError> {
+    CACHED_CALENDAR.lock().unwrap().clear();
+
+    match config().dir {
+        Some(dir) => remove_all(&dir),
+        None => Ok(()),
+    }
+}
+
+/// Возвращает календарь за год из кэша или загружает его из источника.
+///
+/// Устаревшая запись обновляется из источника, а если источник недоступен -
+/// используется как есть. Предварительные календари хранятся только в памяти
+/// и устаревают не позже чем через час.
+pub(crate) fn load(
+    year: u16,
+    source: Arc<dyn CalendarSource>,
+) -> Result<Arc<ProductCalendar>, Box<dyn Error>> {
+    Ok(load_with(&config(), year, source)?.calendar)
+}
+
+/// Официальные нормы за год. Нормы, полученные вместе с днями календаря, хранятся
+/// в той же записи кэша; иначе запрашиваются у источника отдельно и хранятся в памяти.
+pub(crate) fn load_norms(
+    year: u16,
+    source: Arc<dyn CalendarSource>,
+) -> Result<Option<Norms>, Box<dyn Error>> {
+    load_norms_with(&config(), year, source)
+}
+
+fn load_norms_with(
+    config: &CacheConfig,
+    year: u16,
+    source: Arc<dyn CalendarSource>,
+) -> Result<Option<Norms>, Box<dyn Error>> {
+    if let Some(norms) = load_with(config, year, source.clone())?.norms {
+        return Ok(norms);
+    }
+    let norms = source.fetch_norms(year)?;
+    if let Some(entry) = CACHED_CALENDAR
+        .lock()
+        .unwrap()
+        .get_mut(&(source.name(), year))
+        .filter(|_| source.cacheable())
+    {
+        entry.norms = Some(norms.clone());
+    }
+    Ok(norms)
+}
+
+fn load_with(
+    config: &CacheConfig,
+    year: u16,
+    source: Arc<dyn CalendarSource>,
+) -> Result<CachedCalendar, Box<dyn Error>> {
+    let now = unix_now();
+    if !source.cacheable() {
+        let (days, norms) = source.fetch_with_norms(year)?;
+        return Ok(CachedCalendar {
+            fetched_at: now,
+            calendar: Arc::new(ProductCalendar::from_source_data(year, source, days)),
+            norms: norms.map(Some),
+        });
+    }
+
+    let key = (source.name(), year);
+
+    //Блокировка не удерживается во время загрузки: источник может быть медленным
+    //или сам обращаться к кэшу
+    let mut stale = match CACHED_CALENDAR.lock().unwrap().get(&key) {
+        Some(entry) if entry.is_fresh(config, now) => return Ok(entry.clone()),
+        Some(entry) => Some(entry.clone()),
+        None => None,
+    };
+
+    let disk_path = config
+        .dir
+        .as_ref()
+        .filter(|_| source.persistent_cache())
+        .map(|dir| dir.join(sanitize(&key.0)).join(format!("{}.json", year)));
+
+    if stale.is_none() {
+        if let Some(entry) = disk_path.as_deref().and_then(read_entry) {
+            let entry = CachedCalendar {
+                fetched_at: entry.fetched_at,
+                calendar: Arc::new(ProductCalendar::from_source_data(
+                    year,
+                    source.clone(),
+                    entry.days,
+                )),
+                norms: entry.norms.map(Some),
+            };
+            if entry.is_fresh(config, now) {
+                CACHED_CALENDAR.lock().unwrap().insert(key, entry.clone());
+                return Ok(entry);
+            }
+            stale = Some(entry);
+        }
+    }
+
+    let (days, norms) = match source.fetch_with_norms(year) {
+        Ok(data) => data,
+        Err(e) => return stale.ok_or(e),
+    };
+
+    let disk_entry = disk_path.map(|path| {
+        let entry = DiskEntry {
+            fetched_at: now,
+            days: days.clone(),
+            norms: norms.clone(),
+        };
+        (path, entry)
+    });
+    let calendar = ProductCalendar::from_source_data(year, source, days);
+
+    //Предварительный календарь не сохраняется на диск, чтобы его заменили официальные данные
+    if let Some((path, disk_entry)) = disk_entry.filter(|_| !calendar.is_predicted()) {
+        //Ошибка записи на диск (нет прав, нет места) не мешает вернуть загруженный календарь
+        let _ = write_entry(&path, &disk_entry);
+    }
+
+    let entry = CachedCalendar {
+        fetched_at: now,
+        calendar: Arc::new(calendar),
+        norms: norms.map(Some),
+    };
+    CACHED_CALENDAR.lock().unwrap().insert(key, entry.clone());
+    Ok(entry)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+//Имя источника как имя каталога
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn cache_error(e: std::io::Error) -> ProductCalendarError {
+    ProductCalendarError::CacheError(e.to_string())
+}
+
+fn remove_year(dir: &Path, year: u16) -> Result<(), ProductCalendarError> {
+    for source_dir in read_dir(dir)? {
+        let path = source_dir.join(format!("{}.json", year));
+        if path.exists() {
+            fs::remove_file(path).map_err(cache_error)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_all(dir: &Path) -> Result<(), ProductCalendarError> {
+    for source_dir in read_dir(dir)? {
+        fs::remove_dir_all(source_dir).map_err(cache_error)?;
+    }
+    Ok(())
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, ProductCalendarError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir).map_err(cache_error)? {
+        let path = entry.map_err(cache_error)?.path();
+        if path.is_dir() {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs)
+}
+
+//Поврежденная или нечитаемая запись считается отсутствующей
+fn read_entry(path: &Path) -> Option<DiskEntry> {
+    let data = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+//Запись через временный файл, чтобы параллельные процессы не читали её частично
+fn write_entry(path: &Path, entry: &DiskEntry) -> Result<(), ProductCalendarError> {
+    let parent = path.parent().expect("путь записи кэша содержит каталог");
+    fs::create_dir_all(parent).map_err(cache_error)?;
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    let data = serde_json::to_string(entry)
+        .map_err(|e| ProductCalendarError::CacheError(e.to_string()))?;
+    fs::write(&tmp, data).map_err(cache_error)?;
+    fs::rename(&tmp, path).map_err(cache_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::kind::DayKind;
+    use chrono::NaiveDate;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct CountingSource {
+        //У каждого теста свое имя, чтобы параллельные тесты не делили записи кэша
+        name: &'static str,
+        fetches: AtomicUsize,
+        offline: AtomicBool,
+        uncached: bool,
+        predicted: bool,
+    }
+
+    impl CalendarSource for CountingSource {
+        fn name(&self) -> String {
+            self.name.to_string()
+        }
+
+        fn cacheable(&self) -> bool {
+            !self.uncached
+        }
+
+        fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn Error>> {
+            if self.offline.load(Ordering::SeqCst) {
+                return Err("нет сети".into());
+            }
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let mut day = Day::new(NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap());
+            day.set_kind(DayKind::Holiday);
+            day.set_predicted(self.predicted);
+            Ok(vec![day])
+        }
+    }
+
+    #[test]
+    fn test_disk_cache_ttl_and_invalidate() {
+        let dir = std::env::temp_dir().join(format!("product_calendar_cache_{}", unix_now()));
+        let mut config = CacheConfig {
+            dir: Some(dir.clone()),
+            ttl: None,
+        };
+        let source = Arc::new(CountingSource {
+            name: "counting_disk",
+            ..Default::default()
+        });
+        let fetches = || source.fetches.load(Ordering::SeqCst);
+
+        let first = load_with(&config, 2030, source.clone()).unwrap().calendar;
+        let second = load_with(&config, 2030, source.clone()).unwrap().calendar;
+        assert_eq!(fetches(), 1);
+        //Попадание в кэш не копирует дни
+        assert!(Arc::ptr_eq(&first, &second));
+
+        //Новый процесс: памяти нет, календарь читается с диска
+        CACHED_CALENDAR
+            .lock()
+            .unwrap()
+            .retain(|(name, _), _| name != source.name);
+        let pc = load_with(&config, 2030, source.clone()).unwrap().calendar;
+        assert_eq!(fetches(), 1);
+        assert_eq!(pc.first().unwrap().get_kind(), DayKind::Holiday);
+
+        //Устаревшая запись обновляется
+        config.ttl = Some(Duration::ZERO);
+        load_with(&config, 2030, source.clone()).unwrap();
+        assert_eq!(fetches(), 2);
+
+        //Источник недоступен - используется устаревшая запись
+        source.offline.store(true, Ordering::SeqCst);
+        assert!(load_with(&config, 2030, source.clone()).is_ok());
+
+        let path = dir.join("counting_disk").join("2030.json");
+        assert!(path.exists());
+        remove_year(&dir, 2030).unwrap();
+        assert!(!path.exists());
+
+        remove_all(&dir).unwrap();
+        assert!(read_dir(&dir).unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unwritable_dir() {
+        //Каталог кэша указывает на обычный файл
+        let file = std::env::temp_dir().join(format!("product_calendar_file_{}", unix_now()));
+        fs::write(&file, "").unwrap();
+        let config = CacheConfig {
+            dir: Some(file.clone()),
+            ttl: None,
+        };
+        let source = Arc::new(CountingSource {
+            name: "counting_unwritable",
+            ..Default::default()
+        });
+
+        let pc = load_with(&config, 2034, source.clone()).unwrap().calendar;
+        assert_eq!(pc.first().unwrap().get_kind(), DayKind::Holiday);
+        //Календарь сохранен хотя бы в памяти
+        load_with(&config, 2034, source.clone()).unwrap();
+        assert_eq!(source.fetches.load(Ordering::SeqCst), 1);
+        fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_uncacheable_source() {
+        let source = Arc::new(CountingSource {
+            name: "counting_uncached",
+            uncached: true,
+            ..Default::default()
+        });
+        load_with(&CacheConfig::default(), 2031, source.clone()).unwrap();
+        load_with(&CacheConfig::default(), 2031, source.clone()).unwrap();
+        assert_eq!(source.fetches.load(Ordering::SeqCst), 2);
+        assert!(!CACHED_CALENDAR
+            .lock()
+            .unwrap()
+            .contains_key(&(source.name(), 2031)));
+    }
+
+    #[test]
+    fn test_predicted_expires_in_memory() {
+        let source = Arc::new(CountingSource {
+            name: "counting_predicted",
+            predicted: true,
+            ..Default::default()
+        });
+        let config = CacheConfig::default();
+        let pc = load_with(&config, 2032, source.clone()).unwrap().calendar;
+        assert!(pc.is_predicted());
+        load_with(&config, 2032, source.clone()).unwrap();
+        assert_eq!(source.fetches.load(Ordering::SeqCst), 1);
+
+        //Запись старше часа загружается заново даже без `ttl`
+        CACHED_CALENDAR
+            .lock()
+            .unwrap()
+            .get_mut(&(source.name(), 2032))
+            .unwrap()
+            .fetched_at -= PREDICTED_TTL.as_secs();
+        load_with(&config, 2032, source.clone()).unwrap();
+        assert_eq!(source.fetches.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_norms_cached_with_days() {
+        #[derive(Debug)]
+        struct NormsSource {
+            together: bool,
+            norm_fetches: AtomicUsize,
+        }
+
+        impl CalendarSource for NormsSource {
+            fn name(&self) -> String {
+                format!("norms:{}", self.together)
+            }
+
+            fn fetch(&self, _year: u16) -> Result<Vec<Day>, Box<dyn Error>> {
+                Ok(Vec::new())
+            }
+
+            fn fetch_with_norms(
+                &self,
+                year: u16,
+            ) -> Result<(Vec<Day>, Option<Norms>), Box<dyn Error>> {
+                let norms = self.together.then(|| Norms {
+                    year,
+                    periods: Vec::new(),
+                });
+                Ok((self.fetch(year)?, norms))
+            }
+
+            fn fetch_norms(&self, year: u16) -> Result<Option<Norms>, Box<dyn Error>> {
+                self.norm_fetches.fetch_add(1, Ordering::SeqCst);
+                Ok(Some(Norms {
+                    year,
+                    periods: Vec::new(),
+                }))
+            }
+        }
+
+        let config = CacheConfig::default();
+        for together in [true, false] {
+            let source = Arc::new(NormsSource {
+                together,
+                norm_fetches: AtomicUsize::new(0),
+            });
+            for _ in 0..2 {
+                let norms = load_norms_with(&config, 2033, source.clone()).unwrap();
+                assert_eq!(norms.unwrap().year, 2033);
+            }
+            //Нормы, полученные вместе с днями, отдельно не запрашиваются
+            let expected = if together { 0 } else { 1 };
+            assert_eq!(source.norm_fetches.load(Ordering::SeqCst), expected);
+        }
+    }
+}
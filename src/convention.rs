@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Правило переноса даты, выпавшей на нерабочий день (ISDA business day conventions).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BusinessDayConvention {
+    //Ближайший следующий рабочий день
+    #[default]
+    Following,
+    //Следующий рабочий день, если он в том же месяце, иначе предыдущий
+    ModifiedFollowing,
+    //Ближайший предыдущий рабочий день
+    Preceding,
+}
+
+impl fmt::Display for BusinessDayConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusinessDayConvention::Following => write!(f, "Following"),
+            BusinessDayConvention::ModifiedFollowing => write!(f, "ModifiedFollowing"),
+            BusinessDayConvention::Preceding => write!(f, "Preceding"),
+        }
+    }
+}
+
+impl FromStr for BusinessDayConvention {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Following" => Ok(BusinessDayConvention::Following),
+            "ModifiedFollowing" => Ok(BusinessDayConvention::ModifiedFollowing),
+            "Preceding" => Ok(BusinessDayConvention::Preceding),
+            _ => Err(format!("Invalid BusinessDayConvention: '{}'. Available options: 'Following', 'ModifiedFollowing', 'Preceding'", s)),
+        }
+    }
+}
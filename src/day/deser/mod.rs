@@ -1,2 +1,3 @@
 pub mod date;
+pub mod opt_date;
 pub mod weekday;
@@ -0,0 +1,25 @@
+use super::date;
+use chrono::NaiveDate;
+use serde::{self, Deserialize, Deserializer, Serializer};
+
+//Дата в том же формате, что и в модуле `date`
+#[derive(Deserialize)]
+struct DateWrapper(#[serde(with = "date")] NaiveDate);
+
+pub fn serialize<S>(value: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(d) => date::serialize(d, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let wrapper = Option::<DateWrapper>::deserialize(deserializer)?;
+    Ok(wrapper.map(|DateWrapper(d)| d))
+}
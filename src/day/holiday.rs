@@ -0,0 +1,27 @@
+use chrono::{Datelike, NaiveDate};
+
+/// Нерабочие праздничные дни по ст. 112 ТК РФ: (месяц, день, название)
+pub const PUBLIC_HOLIDAYS: [(u32, u32, &str); 14] = [
+    (1, 1, "Новогодние каникулы"),
+    (1, 2, "Новогодние каникулы"),
+    (1, 3, "Новогодние каникулы"),
+    (1, 4, "Новогодние каникулы"),
+    (1, 5, "Новогодние каникулы"),
+    (1, 6, "Новогодние каникулы"),
+    (1, 7, "Рождество Христово"),
+    (1, 8, "Новогодние каникулы"),
+    (2, 23, "День защитника Отечества"),
+    (3, 8, "Международный женский день"),
+    (5, 1, "Праздник Весны и Труда"),
+    (5, 9, "День Победы"),
+    (6, 12, "День России"),
+    (11, 4, "День народного единства"),
+];
+
+/// Название федерального праздника, приходящегося на дату.
+pub fn holiday_name(date: NaiveDate) -> Option<&'static str> {
+    PUBLIC_HOLIDAYS
+        .iter()
+        .find(|(month, day, _)| date.month() == *month && date.day() == *day)
+        .map(|(_, _, name)| *name)
+}
@@ -10,6 +10,21 @@ impl Day {
         day_map.insert("weekday".to_owned(), self.weekday.to_string());
         day_map.insert("day".to_owned(), format!("{}", self.day.format("%Y-%m-%d")));
         day_map.insert("kind".to_owned(), self.kind.to_string());
+        if let Some(name) = &self.name {
+            day_map.insert("name".to_owned(), name.clone());
+        }
+        if let Some(date) = self.transferred_from {
+            day_map.insert(
+                "transferred_from".to_owned(),
+                format!("{}", date.format("%Y-%m-%d")),
+            );
+        }
+        if let Some(date) = self.transferred_to {
+            day_map.insert(
+                "transferred_to".to_owned(),
+                format!("{}", date.format("%Y-%m-%d")),
+            );
+        }
         day_map
     }
 }
@@ -18,9 +33,19 @@ impl fmt::Display for Day {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Day(day={}, kind={}, weekday={})",
+            "Day(day={}, kind={}, weekday={}",
             self.day, self.kind, self.weekday
-        )
+        )?;
+        if let Some(name) = &self.name {
+            write!(f, ", name={}", name)?;
+        }
+        if let Some(date) = self.transferred_from {
+            write!(f, ", transferred_from={}", date)?;
+        }
+        if let Some(date) = self.transferred_to {
+            write!(f, ", transferred_to={}", date)?;
+        }
+        write!(f, ")")
     }
 }
 
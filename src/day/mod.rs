@@ -1,8 +1,9 @@
 pub mod deser;
+pub mod holiday;
 pub mod impls;
 pub mod kind;
 
-use self::deser::{date, weekday};
+use self::deser::{date, opt_date, weekday};
 use self::kind::DayKind;
 use chrono::{Datelike, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,18 @@ pub struct Day {
     #[serde(with = "date")]
     day: NaiveDate,
     kind: DayKind,
+    //День рассчитан по правилам ТК РФ, а не взят из официального календаря
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    predicted: bool,
+    //Название праздника или причина изменения типа дня
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    //Выходной перенесен на этот день с указанной даты
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_date")]
+    transferred_from: Option<NaiveDate>,
+    //Выходной с этого дня перенесен на указанную дату
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "opt_date")]
+    transferred_to: Option<NaiveDate>,
 }
 
 impl Day {
@@ -24,7 +37,15 @@ impl Day {
             Weekday::Sat | Weekday::Sun => DayKind::Weekend,
             _ => DayKind::Work,
         };
-        Day { day, weekday, kind }
+        Day {
+            day,
+            weekday,
+            kind,
+            predicted: false,
+            name: None,
+            transferred_from: None,
+            transferred_to: None,
+        }
     }
 
     pub fn get_date(&self) -> NaiveDate {
@@ -51,6 +72,45 @@ impl Day {
     pub fn set_kind(&mut self, kind: DayKind) {
         self.kind = kind;
     }
+
+    pub fn is_predicted(&self) -> bool {
+        self.predicted
+    }
+
+    pub fn set_predicted(&mut self, predicted: bool) {
+        self.predicted = predicted;
+    }
+
+    /// Нерабочий праздничный день с названием (ст. 112 ТК РФ, региональный или корпоративный),
+    /// в отличие от выходного, перенесенного на будний день. Праздник, совпавший с выходным,
+    /// хранится как `Weekend` с названием и тоже считается праздничным днем.
+    pub fn is_named_holiday(&self) -> bool {
+        self.name.is_some() && matches!(self.kind, DayKind::Holiday | DayKind::Weekend)
+    }
+
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    pub fn get_transferred_from(&self) -> Option<NaiveDate> {
+        self.transferred_from
+    }
+
+    pub fn set_transferred_from(&mut self, date: Option<NaiveDate>) {
+        self.transferred_from = date;
+    }
+
+    pub fn get_transferred_to(&self) -> Option<NaiveDate> {
+        self.transferred_to
+    }
+
+    pub fn set_transferred_to(&mut self, date: Option<NaiveDate>) {
+        self.transferred_to = date;
+    }
 }
 
 #[cfg(test)]
@@ -103,6 +163,18 @@ mod tests {
         assert_eq!(&serialized[..], expected);
     }
 
+    #[test]
+    fn test_deserialize_transfer() {
+        let data = r#"{"weekday":"Mon","day":"2024-04-29","kind":"Holiday","transferred_from":"2024-04-27"}"#;
+        let d: Day = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            d.get_transferred_from(),
+            NaiveDate::from_ymd_opt(2024, 4, 27)
+        );
+        assert_eq!(d.get_transferred_to(), None);
+        assert_eq!(serde_json::to_string(&d).unwrap(), data);
+    }
+
     #[test]
     fn test_day_as_map() {
         let d = Day::new(NaiveDate::from_ymd_opt(2024, 5, 6).unwrap());
@@ -0,0 +1,205 @@
+use crate::convention::BusinessDayConvention;
+use crate::day::{kind::DayKind, Day};
+use crate::errors::ProductCalendarError;
+use crate::pc::ProductCalendar;
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Единица исчисления срока (ст. 190-192 ГК РФ, ст. 6.1 НК РФ).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PeriodUnit {
+    #[default]
+    CalendarDays,
+    WorkDays,
+    Months,
+    Years,
+}
+
+impl PeriodUnit {
+    //Название единицы в нужном числе: "1 рабочий день", "3 рабочих дня", "5 рабочих дней"
+    fn label(&self, length: u32) -> &'static str {
+        let forms = match self {
+            PeriodUnit::CalendarDays => ["календарный день", "календарных дня", "календарных дней"],
+            PeriodUnit::WorkDays => ["рабочий день", "рабочих дня", "рабочих дней"],
+            PeriodUnit::Months => ["месяц", "месяца", "месяцев"],
+            PeriodUnit::Years => ["год", "года", "лет"],
+        };
+        match (length % 10, length % 100) {
+            (_, 11..=14) => forms[2],
+            (1, _) => forms[0],
+            (2..=4, _) => forms[1],
+            _ => forms[2],
+        }
+    }
+}
+
+impl fmt::Display for PeriodUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeriodUnit::CalendarDays => write!(f, "CalendarDays"),
+            PeriodUnit::WorkDays => write!(f, "WorkDays"),
+            PeriodUnit::Months => write!(f, "Months"),
+            PeriodUnit::Years => write!(f, "Years"),
+        }
+    }
+}
+
+impl FromStr for PeriodUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "CalendarDays" => Ok(PeriodUnit::CalendarDays),
+            "WorkDays" => Ok(PeriodUnit::WorkDays),
+            "Months" => Ok(PeriodUnit::Months),
+            "Years" => Ok(PeriodUnit::Years),
+            _ => Err(format!("Invalid PeriodUnit: '{}'. Available options: 'CalendarDays', 'WorkDays', 'Months', 'Years'", s)),
+        }
+    }
+}
+
+/// Окончание срока и обоснование его расчета.
+#[derive(Debug, Clone)]
+pub struct Deadline {
+    /// Дата события, с которым связано начало срока.
+    pub start: NaiveDate,
+    pub length: u32,
+    pub unit: PeriodUnit,
+    /// Дата истечения срока без учета переноса.
+    pub nominal_end: NaiveDate,
+    /// Последний день срока с учетом переноса на рабочий день.
+    pub end: Day,
+    pub explanation: String,
+}
+
+impl Deadline {
+    pub fn is_shifted(&self) -> bool {
+        self.nominal_end != self.end.get_date()
+    }
+}
+
+//"День Победы" для праздника, "выходной день" - для субботы или воскресенья
+fn describe(day: &Day) -> String {
+    match (day.get_name(), day.get_kind()) {
+        (Some(name), _) => format!("нерабочий день ({})", name),
+        (None, DayKind::Holiday) => "нерабочий праздничный день".to_string(),
+        (None, _) => "выходной день".to_string(),
+    }
+}
+
+/// Рассчитывает последний день срока длиной `length` единиц `unit`,
+/// начинающегося на следующий день после `start` (ст. 191 ГК РФ, п. 2 ст. 6.1 НК РФ).
+///
+/// * срок в днях истекает в последний день периода;
+/// * срок в рабочих днях считается только по рабочим дням (п. 6 ст. 6.1 НК РФ);
+/// * срок в месяцах и годах истекает в соответствующее число последнего месяца,
+///   а если такого числа нет - в последний день месяца (ст. 192 ГК РФ);
+/// * окончание срока, выпавшее на нерабочий день, переносится на ближайший следующий
+///   рабочий день (ст. 193 ГК РФ, п. 7 ст. 6.1 НК РФ).
+pub fn deadline(
+    pc: &ProductCalendar,
+    start: NaiveDate,
+    length: u32,
+    unit: PeriodUnit,
+) -> Result<Deadline, ProductCalendarError> {
+    let out_of_range = || ProductCalendarError::DateOutOfRange(start.to_string());
+    let nominal_end = match unit {
+        PeriodUnit::CalendarDays => start.checked_add_days(Days::new(length as u64)),
+        PeriodUnit::WorkDays => Some(pc.add_work_days(start, length as i64)?.get_date()),
+        PeriodUnit::Months => start.checked_add_months(Months::new(length)),
+        PeriodUnit::Years => length
+            .checked_mul(12)
+            .and_then(|months| start.checked_add_months(Months::new(months))),
+    }
+    .ok_or_else(out_of_range)?;
+
+    let mut explanation = format!(
+        "Срок {} {} исчисляется со дня, следующего за {}, и истекает {}.",
+        length,
+        unit.label(length),
+        start,
+        nominal_end
+    );
+    if unit == PeriodUnit::WorkDays {
+        explanation
+            .push_str(" Нерабочие дни при исчислении срока не учитываются (п. 6 ст. 6.1 НК РФ).");
+    }
+
+    let nominal = pc.day_at(nominal_end)?;
+    let end = pc.adjust(nominal_end, BusinessDayConvention::Following)?;
+    if end.get_date() != nominal_end {
+        explanation.push_str(&format!(
+            " {} - {}, окончание срока переносится на ближайший следующий рабочий день {} \
+             (ст. 193 ГК РФ, п. 7 ст. 6.1 НК РФ).",
+            nominal_end,
+            describe(&nominal),
+            end.get_date()
+        ));
+    }
+
+    Ok(Deadline {
+        start,
+        length,
+        unit,
+        nominal_end,
+        end,
+        explanation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pc::get_product_calendar;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_days_shifted() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        let result = deadline(&pc, date(2024, 4, 29), 10, PeriodUnit::CalendarDays).unwrap();
+
+        assert_eq!(result.nominal_end, date(2024, 5, 9));
+        assert_eq!(result.end.get_date(), date(2024, 5, 13));
+        assert!(result.is_shifted());
+        assert!(result.explanation.contains("10 календарных дней"));
+        assert!(result.explanation.contains("День Победы"));
+        assert!(result.explanation.contains("ст. 193 ГК РФ"));
+    }
+
+    #[test]
+    fn test_work_days() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        let result = deadline(&pc, date(2024, 5, 6), 5, PeriodUnit::WorkDays).unwrap();
+
+        assert_eq!(result.end.get_date(), date(2024, 5, 15));
+        assert!(!result.is_shifted());
+    }
+
+    #[test]
+    fn test_months_and_years() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+
+        //В феврале нет 31 числа - срок истекает в последний день месяца
+        let result = deadline(&pc, date(2024, 1, 31), 1, PeriodUnit::Months).unwrap();
+        assert_eq!(result.end.get_date(), date(2024, 2, 29));
+
+        //2025-03-01 - суббота
+        let result = deadline(&pc, date(2024, 3, 1), 1, PeriodUnit::Years).unwrap();
+        assert_eq!(result.nominal_end, date(2025, 3, 1));
+        assert_eq!(result.end.get_date(), date(2025, 3, 3));
+        assert!(result.explanation.contains("выходной день"));
+    }
+
+    #[test]
+    fn test_unit_label() {
+        assert_eq!(PeriodUnit::WorkDays.label(1), "рабочий день");
+        assert_eq!(PeriodUnit::WorkDays.label(3), "рабочих дня");
+        assert_eq!(PeriodUnit::WorkDays.label(11), "рабочих дней");
+        assert_eq!(PeriodUnit::Years.label(21), "год");
+    }
+}
@@ -0,0 +1,339 @@
+use crate::day::{kind::DayKind, Day};
+use crate::errors::ProductCalendarError;
+use crate::pc::ProductCalendar;
+use crate::statistic::{Statistic, WeekLength};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Причина отсутствия сотрудника на работе.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AbsenceKind {
+    //Ежегодный оплачиваемый отпуск
+    Vacation,
+    SickLeave,
+    //Сотрудник работает вне офиса, рабочие дни считаются отработанными
+    BusinessTrip,
+    //Отпуск без сохранения заработной платы
+    UnpaidLeave,
+}
+
+impl AbsenceKind {
+    /// Считаются ли рабочие дни периода отсутствия отработанными.
+    pub fn is_worked(&self) -> bool {
+        matches!(self, AbsenceKind::BusinessTrip)
+    }
+}
+
+impl fmt::Display for AbsenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbsenceKind::Vacation => write!(f, "Vacation"),
+            AbsenceKind::SickLeave => write!(f, "SickLeave"),
+            AbsenceKind::BusinessTrip => write!(f, "BusinessTrip"),
+            AbsenceKind::UnpaidLeave => write!(f, "UnpaidLeave"),
+        }
+    }
+}
+
+impl FromStr for AbsenceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Vacation" => Ok(AbsenceKind::Vacation),
+            "SickLeave" => Ok(AbsenceKind::SickLeave),
+            "BusinessTrip" => Ok(AbsenceKind::BusinessTrip),
+            "UnpaidLeave" => Ok(AbsenceKind::UnpaidLeave),
+            _ => Err(format!("Invalid AbsenceKind: '{}'. Available options: 'Vacation', 'SickLeave', 'BusinessTrip', 'UnpaidLeave'", s)),
+        }
+    }
+}
+
+/// Отсутствие сотрудника с `start` по `end` включительно.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Absence {
+    pub kind: AbsenceKind,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+/// День личного календаря: день производственного календаря и отсутствие сотрудника.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmployeeDay {
+    pub day: Day,
+    pub absence: Option<AbsenceKind>,
+}
+
+impl EmployeeDay {
+    /// Рабочий по календарю день, в который сотрудник работал.
+    pub fn is_worked(&self) -> bool {
+        matches!(self.day.get_kind(), DayKind::Work | DayKind::Preholiday)
+            && self.absence.is_none_or(|kind| kind.is_worked())
+    }
+}
+
+/// Статистика личного календаря за период.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmployeeStatistic {
+    /// Статистика производственного календаря за период.
+    pub calendar: Statistic,
+    /// Отработанные рабочие и предпраздничные дни.
+    pub worked: Statistic,
+    //Календарные дни отпуска без нерабочих праздничных дней (ст. 120 ТК РФ)
+    pub vacation: u16,
+    pub sick_leave: u16,
+    pub business_trip: u16,
+    pub unpaid_leave: u16,
+}
+
+impl EmployeeStatistic {
+    /// Количество дней отсутствия указанного типа.
+    pub fn absences(&self, kind: AbsenceKind) -> u16 {
+        match kind {
+            AbsenceKind::Vacation => self.vacation,
+            AbsenceKind::SickLeave => self.sick_leave,
+            AbsenceKind::BusinessTrip => self.business_trip,
+            AbsenceKind::UnpaidLeave => self.unpaid_leave,
+        }
+    }
+
+    pub fn worked_days(&self) -> u16 {
+        self.worked.work_days + self.worked.preholidays
+    }
+
+    /// Отработанные часы по норме для рабочей недели `week`.
+    pub fn worked_hours(&self, week: WeekLength) -> f64 {
+        self.worked.work_hours_norm(week)
+    }
+}
+
+/// Личный календарь сотрудника: производственный календарь и периоды отсутствия.
+#[derive(Debug, Clone)]
+pub struct EmployeeCalendar {
+    calendar: ProductCalendar,
+    //Отсортированы по началу, не пересекаются
+    absences: Vec<Absence>,
+}
+
+impl EmployeeCalendar {
+    pub fn new(calendar: ProductCalendar) -> Self {
+        Self {
+            calendar,
+            absences: Vec::new(),
+        }
+    }
+
+    pub fn calendar(&self) -> &ProductCalendar {
+        &self.calendar
+    }
+
+    pub fn absences(&self) -> &[Absence] {
+        &self.absences
+    }
+
+    /// Добавляет период отсутствия. Периоды разных отсутствий не должны пересекаться.
+    pub fn add_absence(
+        &mut self,
+        kind: AbsenceKind,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<(), ProductCalendarError> {
+        if start > end {
+            return Err(ProductCalendarError::AbsenceError(format!(
+                "начало {} позже окончания {}",
+                start, end
+            )));
+        }
+        let idx = self.absences.partition_point(|a| a.start < start);
+        let overlapping = [idx.checked_sub(1), Some(idx)]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.absences.get(i))
+            .find(|a| a.start <= end && start <= a.end);
+        if let Some(other) = overlapping {
+            return Err(ProductCalendarError::AbsenceError(format!(
+                "{} с {} по {} пересекается с {} с {} по {}",
+                kind, start, end, other.kind, other.start, other.end
+            )));
+        }
+        self.absences.insert(idx, Absence { kind, start, end });
+        Ok(())
+    }
+
+    pub fn absence_on(&self, date: NaiveDate) -> Option<AbsenceKind> {
+        let idx = self.absences.partition_point(|a| a.start <= date);
+        idx.checked_sub(1)
+            .map(|i| self.absences[i])
+            .filter(|a| date <= a.end)
+            .map(|a| a.kind)
+    }
+
+    /// Дни личного календаря с `start` по `end` включительно.
+    pub fn days_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<EmployeeDay>, ProductCalendarError> {
+        Ok(self
+            .calendar
+            .period_slice(start, end)?
+            .into_iter()
+            .map(|day| EmployeeDay {
+                absence: self.absence_on(day.get_date()),
+                day,
+            })
+            .collect())
+    }
+
+    pub fn statistic_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<EmployeeStatistic, ProductCalendarError> {
+        let mut statistic = EmployeeStatistic {
+            calendar: self.calendar.statistic_between(start, end)?,
+            ..Default::default()
+        };
+        for employee_day in self.days_between(start, end)? {
+            if employee_day.is_worked() {
+                match employee_day.day.get_kind() {
+                    DayKind::Preholiday => statistic.worked.preholidays += 1,
+                    _ => statistic.worked.work_days += 1,
+                }
+            }
+            match employee_day.absence {
+                Some(AbsenceKind::Vacation) if !employee_day.day.is_named_holiday() => {
+                    statistic.vacation += 1
+                }
+                Some(AbsenceKind::SickLeave) => statistic.sick_leave += 1,
+                Some(AbsenceKind::BusinessTrip) => statistic.business_trip += 1,
+                Some(AbsenceKind::UnpaidLeave) => statistic.unpaid_leave += 1,
+                _ => {}
+            }
+        }
+        Ok(statistic)
+    }
+
+    pub fn worked_days_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<u16, ProductCalendarError> {
+        Ok(self.statistic_between(start, end)?.worked_days())
+    }
+
+    pub fn worked_hours_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        week: WeekLength,
+    ) -> Result<f64, ProductCalendarError> {
+        Ok(self.statistic_between(start, end)?.worked_hours(week))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pc::get_product_calendar;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn employee() -> EmployeeCalendar {
+        let mut employee = EmployeeCalendar::new(get_product_calendar(Some(2024)).unwrap());
+        employee
+            .add_absence(AbsenceKind::Vacation, date(2024, 5, 6), date(2024, 5, 19))
+            .unwrap();
+        employee
+            .add_absence(AbsenceKind::SickLeave, date(2024, 5, 20), date(2024, 5, 22))
+            .unwrap();
+        employee
+            .add_absence(
+                AbsenceKind::BusinessTrip,
+                date(2024, 5, 27),
+                date(2024, 5, 28),
+            )
+            .unwrap();
+        employee
+    }
+
+    #[test]
+    fn test_absences() {
+        let mut employee = employee();
+
+        assert_eq!(
+            employee.absence_on(date(2024, 5, 6)),
+            Some(AbsenceKind::Vacation)
+        );
+        assert_eq!(
+            employee.absence_on(date(2024, 5, 22)),
+            Some(AbsenceKind::SickLeave)
+        );
+        assert_eq!(employee.absence_on(date(2024, 5, 23)), None);
+        assert_eq!(employee.absence_on(date(2024, 5, 5)), None);
+
+        assert!(employee
+            .add_absence(
+                AbsenceKind::UnpaidLeave,
+                date(2024, 5, 19),
+                date(2024, 5, 20)
+            )
+            .is_err());
+        assert!(employee
+            .add_absence(
+                AbsenceKind::UnpaidLeave,
+                date(2024, 5, 24),
+                date(2024, 5, 23)
+            )
+            .is_err());
+        employee
+            .add_absence(
+                AbsenceKind::UnpaidLeave,
+                date(2024, 5, 23),
+                date(2024, 5, 24),
+            )
+            .unwrap();
+        assert_eq!(employee.absences().len(), 4);
+    }
+
+    #[test]
+    fn test_statistic() {
+        let statistic = employee()
+            .statistic_between(date(2024, 5, 1), date(2024, 5, 31))
+            .unwrap();
+
+        assert_eq!(
+            statistic.calendar.work_days + statistic.calendar.preholidays,
+            20
+        );
+        //9 мая не входит в отпуск, 10 мая - перенесенный выходной входит
+        assert_eq!(statistic.vacation, 13);
+        assert_eq!(statistic.absences(AbsenceKind::SickLeave), 3);
+        assert_eq!(statistic.business_trip, 2);
+        assert_eq!(statistic.unpaid_leave, 0);
+        //Из 20 рабочих дней мая 8 в отпуске, 3 на больничном, командировка отработана
+        assert_eq!(statistic.worked_days(), 9);
+        assert_eq!(statistic.worked_hours(WeekLength::Forty), 72.0);
+    }
+
+    #[test]
+    fn test_vacation_over_weekend_holidays() {
+        //Новогодние каникулы 2024 и 8 марта 2026 (воскресенье) в дни отпуска не входят
+        for (year, start, end, expected) in [
+            (2024, date(2024, 1, 1), date(2024, 1, 8), 0),
+            (2024, date(2024, 1, 1), date(2024, 1, 14), 6),
+            (2026, date(2026, 3, 2), date(2026, 3, 15), 13),
+        ] {
+            let mut employee = EmployeeCalendar::new(get_product_calendar(Some(year)).unwrap());
+            employee
+                .add_absence(AbsenceKind::Vacation, start, end)
+                .unwrap();
+            let statistic = employee.statistic_between(start, end).unwrap();
+            assert_eq!(statistic.vacation, expected);
+        }
+    }
+}
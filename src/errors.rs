@@ -14,4 +14,22 @@ pub enum ProductCalendarError {
     ExceedMaxDaysError(usize),
     #[error("Неверно указан квартал:`{0}. Должен быть от 1 до 4 включительно.")]
     InvalidQuarter(u8),
+    #[error("Неверно указан месяц: `{0}`. Должен быть от 1 до 12 включительно.")]
+    InvalidMonth(u8),
+    #[error("Неверно указано полугодие: `{0}`. Должно быть 1 или 2.")]
+    InvalidHalfYear(u8),
+    #[error("Неверная продолжительность рабочей недели: `{0}`. Допустимо 40, 36 или 24 часа.")]
+    InvalidWeekLength(u16),
+    #[error("Нормы рабочего времени не совпадают с календарем: {0}")]
+    NormMismatch(String),
+    #[error("Ошибка кэша календаря: {0}")]
+    CacheError(String),
+    #[error("Ошибка корпоративных изменений календаря: {0}")]
+    OverlayError(String),
+    #[error("Некорректный рабочий распорядок: {0}")]
+    ScheduleError(String),
+    #[error("Некорректное отработанное время за `{0}`: {1} ч. Должно быть от 0 до 24 часов.")]
+    InvalidWorkedHours(String, f64),
+    #[error("Некорректный период отсутствия: {0}")]
+    AbsenceError(String),
 }
@@ -0,0 +1,258 @@
+use crate::errors::ProductCalendarError;
+use crate::pc::ProductCalendar;
+use crate::statistic::WeekLength;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const MONTH_NAMES: [&str; 12] = [
+    "Январь",
+    "Февраль",
+    "Март",
+    "Апрель",
+    "Май",
+    "Июнь",
+    "Июль",
+    "Август",
+    "Сентябрь",
+    "Октябрь",
+    "Ноябрь",
+    "Декабрь",
+];
+
+/// Период официальной таблицы норм рабочего времени.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NormPeriod {
+    Month(u8),
+    Quarter(u8),
+    HalfYear(u8),
+    Year,
+}
+
+impl NormPeriod {
+    /// Распознает заголовок столбца таблицы: "Январь", "I квартал", "1 полугодие", "2024 г."
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim();
+        let lower = label.to_lowercase();
+        if let Some(idx) = MONTH_NAMES.iter().position(|m| m.to_lowercase() == lower) {
+            return Some(NormPeriod::Month(idx as u8 + 1));
+        }
+
+        let number = lower.split_whitespace().next().and_then(|n| match n {
+            "1" | "i" => Some(1),
+            "2" | "ii" => Some(2),
+            "3" | "iii" => Some(3),
+            "4" | "iv" => Some(4),
+            _ => None,
+        });
+        if lower.contains("полугод") {
+            return number.filter(|n| *n <= 2).map(NormPeriod::HalfYear);
+        }
+        if lower.contains("квартал") {
+            return number.map(NormPeriod::Quarter);
+        }
+        if lower.contains("год") || lower.ends_with(" г.") {
+            return Some(NormPeriod::Year);
+        }
+        None
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        let month = date.month() as u8;
+        match self {
+            NormPeriod::Month(m) => month == *m,
+            NormPeriod::Quarter(q) => (month - 1) / 3 + 1 == *q,
+            NormPeriod::HalfYear(h) => (month - 1) / 6 + 1 == *h,
+            NormPeriod::Year => true,
+        }
+    }
+
+    fn bounds(&self, year: i32) -> (NaiveDate, NaiveDate) {
+        let (first_month, last_month) = match self {
+            NormPeriod::Month(m) => (*m as u32, *m as u32),
+            NormPeriod::Quarter(q) => (*q as u32 * 3 - 2, *q as u32 * 3),
+            NormPeriod::HalfYear(h) => (*h as u32 * 6 - 5, *h as u32 * 6),
+            NormPeriod::Year => (1, 12),
+        };
+        let start = NaiveDate::from_ymd_opt(year, first_month, 1).unwrap();
+        let end = match last_month {
+            12 => NaiveDate::from_ymd_opt(year, 12, 31).unwrap(),
+            m => NaiveDate::from_ymd_opt(year, m + 1, 1)
+                .unwrap()
+                .pred_opt()
+                .unwrap(),
+        };
+        (start, end)
+    }
+}
+
+impl fmt::Display for NormPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const ROMAN: [&str; 4] = ["I", "II", "III", "IV"];
+        match self {
+            NormPeriod::Month(m) => write!(f, "{}", MONTH_NAMES[*m as usize - 1]),
+            NormPeriod::Quarter(q) => write!(f, "{} квартал", ROMAN[*q as usize - 1]),
+            NormPeriod::HalfYear(h) => write!(f, "{} полугодие", h),
+            NormPeriod::Year => write!(f, "Год"),
+        }
+    }
+}
+
+/// Строка официальной таблицы за один период.
+/// Значения, отсутствующие в таблице, равны `None`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct PeriodNorm {
+    pub calendar_days: Option<u16>,
+    pub work_days: Option<u16>,
+    pub rest_days: Option<u16>,
+    pub hours_40: Option<f64>,
+    pub hours_36: Option<f64>,
+    pub hours_24: Option<f64>,
+}
+
+impl PeriodNorm {
+    pub fn hours(&self, week: WeekLength) -> Option<f64> {
+        match week {
+            WeekLength::Forty => self.hours_40,
+            WeekLength::ThirtySix => self.hours_36,
+            WeekLength::TwentyFour => self.hours_24,
+        }
+    }
+}
+
+/// Официальные нормы рабочего времени за год по месяцам, кварталам и полугодиям.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Norms {
+    pub year: u16,
+    pub periods: Vec<(NormPeriod, PeriodNorm)>,
+}
+
+impl Norms {
+    pub fn get(&self, period: NormPeriod) -> Option<&PeriodNorm> {
+        self.periods
+            .iter()
+            .find(|(p, _)| *p == period)
+            .map(|(_, norm)| norm)
+    }
+
+    /// Сверяет нормы с днями календаря.
+    ///
+    /// Проверяются только периоды, все дни которых есть в календаре.
+    /// При расхождении возвращает `NormMismatch` со списком всех различий.
+    pub fn validate(&self, pc: &ProductCalendar) -> Result<(), ProductCalendarError> {
+        let mut mismatches = Vec::new();
+
+        for (period, norm) in &self.periods {
+            let (start, end) = period.bounds(self.year as i32);
+            let part: ProductCalendar = pc
+                .into_iter()
+                .filter(|d| d.get_date() >= start && d.get_date() <= end)
+                .cloned()
+                .collect();
+            //Прореженный календарь (например, `by_kind`) содержит не все дни периода
+            if part.total_days() as i64 != (end - start).num_days() + 1 {
+                continue;
+            }
+            let statistic = part.statistic();
+
+            let mut check = |name: &str, official: Option<f64>, computed: f64| {
+                if let Some(official) = official {
+                    if (official - computed).abs() > 0.05 {
+                        mismatches.push(format!(
+                            "{} {}: {} по таблице, {} по календарю",
+                            period, name, official, computed
+                        ));
+                    }
+                }
+            };
+            check(
+                "календарные дни",
+                norm.calendar_days.map(f64::from),
+                part.total_days() as f64,
+            );
+            check(
+                "рабочие дни",
+                norm.work_days.map(f64::from),
+                (statistic.work_days + statistic.preholidays) as f64,
+            );
+            check(
+                "выходные и праздничные дни",
+                norm.rest_days.map(f64::from),
+                statistic.rest_days() as f64,
+            );
+            for week in [
+                WeekLength::Forty,
+                WeekLength::ThirtySix,
+                WeekLength::TwentyFour,
+            ] {
+                check(
+                    &format!("часы при {}-часовой неделе", week.hours()),
+                    norm.hours(week),
+                    statistic.work_hours_norm(week),
+                );
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(ProductCalendarError::NormMismatch(mismatches.join("; ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::kind::DayKind;
+    use crate::pc::get_product_calendar;
+
+    #[test]
+    fn test_period_labels() {
+        assert_eq!(NormPeriod::from_label("Январь"), Some(NormPeriod::Month(1)));
+        assert_eq!(
+            NormPeriod::from_label(" IV квартал "),
+            Some(NormPeriod::Quarter(4))
+        );
+        assert_eq!(
+            NormPeriod::from_label("2 полугодие"),
+            Some(NormPeriod::HalfYear(2))
+        );
+        assert_eq!(NormPeriod::from_label("2024 г."), Some(NormPeriod::Year));
+        assert_eq!(NormPeriod::from_label("Рабочие дни"), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        let january = PeriodNorm {
+            calendar_days: Some(31),
+            work_days: Some(17),
+            rest_days: Some(14),
+            hours_40: Some(136.0),
+            hours_36: Some(122.4),
+            hours_24: Some(81.6),
+        };
+        let mut norms = Norms {
+            year: 2024,
+            periods: vec![
+                (NormPeriod::Month(1), january),
+                (
+                    NormPeriod::Year,
+                    PeriodNorm {
+                        work_days: Some(248),
+                        hours_40: Some(1979.0),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        };
+        assert!(norms.validate(&pc).is_ok());
+
+        norms.periods[0].1.work_days = Some(16);
+        let err = norms.validate(&pc).unwrap_err().to_string();
+        assert!(err.contains("Январь рабочие дни: 16 по таблице, 17 по календарю"));
+        //В календаре только рабочие дни - периоды не сверяются
+        assert!(norms.validate(&pc.by_kind(DayKind::Work)).is_ok());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::day::deser::date;
+use crate::day::{kind::DayKind, Day};
+use crate::errors::ProductCalendarError;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Изменение типа дня, установленное организацией.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+    #[serde(with = "date")]
+    pub date: NaiveDate,
+    pub kind: DayKind,
+    pub reason: String,
+}
+
+/// Корпоративные изменения поверх официального календаря:
+/// дополнительные выходные, рабочие субботы и т.п.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overlay {
+    //Отсортированы по дате, одна запись на дату
+    overrides: Vec<Override>,
+}
+
+impl Overlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Добавляет изменение, заменяя существующее на ту же дату.
+    pub fn add(&mut self, date: NaiveDate, kind: DayKind, reason: &str) {
+        let entry = Override {
+            date,
+            kind,
+            reason: reason.to_owned(),
+        };
+        match self.overrides.binary_search_by_key(&date, |o| o.date) {
+            Ok(idx) => self.overrides[idx] = entry,
+            Err(idx) => self.overrides.insert(idx, entry),
+        }
+    }
+
+    pub fn remove(&mut self, date: NaiveDate) -> Option<Override> {
+        self.overrides
+            .binary_search_by_key(&date, |o| o.date)
+            .ok()
+            .map(|idx| self.overrides.remove(idx))
+    }
+
+    pub fn get(&self, date: NaiveDate) -> Option<&Override> {
+        self.overrides
+            .binary_search_by_key(&date, |o| o.date)
+            .ok()
+            .map(|idx| &self.overrides[idx])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Override> {
+        self.overrides.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.overrides.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+
+    /// Добавляет изменения из `other`, при совпадении дат приоритет у `other`.
+    pub fn extend(&mut self, other: &Overlay) {
+        for o in other.iter() {
+            self.add(o.date, o.kind, &o.reason);
+        }
+    }
+
+    pub fn apply(&self, days: &mut [Day]) {
+        for day in days.iter_mut() {
+            if let Some(o) = self.get(day.get_date()) {
+                day.set_kind(o.kind);
+                //Официальное название праздника не заменяется причиной изменения
+                if !o.reason.is_empty() && day.get_name().is_none() {
+                    day.set_name(Some(o.reason.clone()));
+                }
+            }
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProductCalendarError> {
+        let data = fs::read_to_string(path)
+            .map_err(|e| ProductCalendarError::OverlayError(e.to_string()))?;
+        serde_json::from_str(&data).map_err(|e| ProductCalendarError::OverlayError(e.to_string()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ProductCalendarError> {
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|e| ProductCalendarError::OverlayError(e.to_string()))?;
+        fs::write(path, data).map_err(|e| ProductCalendarError::OverlayError(e.to_string()))
+    }
+}
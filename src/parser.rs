@@ -1,12 +1,13 @@
 use crate::day::{kind::DayKind, Day};
 use crate::errors::ProductCalendarError;
-use chrono::NaiveDate;
+use crate::norms::{NormPeriod, Norms, PeriodNorm};
+use chrono::{Datelike, NaiveDate};
 use reqwest::blocking::Client;
 use reqwest::header::USER_AGENT;
 use scraper::{ElementRef, Html, Selector};
 use std::collections::HashMap;
 
-const URL: &str = "https://www.consultant.ru/law/ref/calendar/proizvodstvennye";
+pub const URL: &str = "https://www.consultant.ru/law/ref/calendar/proizvodstvennye";
 const MONTHS: [&str; 12] = [
     "Январь",
     "Февраль",
@@ -21,6 +22,28 @@ const MONTHS: [&str; 12] = [
     "Ноябрь",
     "Декабрь",
 ];
+//Месяцы в датах переносов: "с субботы 27 апреля на понедельник 29 апреля"
+const MONTHS_GENITIVE: [&str; 12] = [
+    "января",
+    "февраля",
+    "марта",
+    "апреля",
+    "мая",
+    "июня",
+    "июля",
+    "августа",
+    "сентября",
+    "октября",
+    "ноября",
+    "декабря",
+];
+
+/// Дни календаря и официальные нормы, полученные с одной страницы.
+#[derive(Debug, Clone)]
+pub struct ParsedCalendar {
+    pub days: Vec<Day>,
+    pub norms: Norms,
+}
 
 #[derive(Debug)]
 pub struct ProductCalendarParser {
@@ -31,6 +54,11 @@ pub struct ProductCalendarParser {
 
 impl ProductCalendarParser {
     pub fn new(year: u16) -> ProductCalendarParser {
+        Self::with_url(year, URL)
+    }
+
+    //Парсер для зеркала consultant.ru с той же разметкой страницы
+    pub fn with_url(year: u16, base_url: &str) -> ProductCalendarParser {
         let months: HashMap<&'static str, u8> = MONTHS
             .iter()
             .enumerate()
@@ -40,7 +68,7 @@ impl ProductCalendarParser {
         Self {
             months,
             year,
-            url: format!("{}/{}", URL, year),
+            url: format!("{}/{}", base_url.trim_end_matches('/'), year),
         }
     }
 
@@ -68,6 +96,25 @@ impl ProductCalendarParser {
     }
 
     pub fn parse_calendar(&mut self) -> Result<Vec<Day>, Box<dyn std::error::Error>> {
+        let document = self.fetch_document()?;
+        self.days_from_document(&document)
+    }
+
+    pub fn parse_norms(&mut self) -> Result<Norms, Box<dyn std::error::Error>> {
+        let document = self.fetch_document()?;
+        self.norms_from_document(&document)
+    }
+
+    //Дни и нормы за один запрос к странице
+    pub fn parse(&mut self) -> Result<ParsedCalendar, Box<dyn std::error::Error>> {
+        let document = self.fetch_document()?;
+        Ok(ParsedCalendar {
+            days: self.days_from_document(&document)?,
+            norms: self.norms_from_document(&document)?,
+        })
+    }
+
+    fn fetch_document(&self) -> Result<Html, Box<dyn std::error::Error>> {
         let client = Client::new();
         let response = client
             .get(&self.url)
@@ -81,8 +128,10 @@ impl ProductCalendarParser {
         };
 
         let body = response.text()?;
+        Ok(Html::parse_document(&body))
+    }
 
-        let document = Html::parse_document(&body);
+    fn days_from_document(&self, document: &Html) -> Result<Vec<Day>, Box<dyn std::error::Error>> {
         let month_selector = Selector::parse(".month")?;
         let holiday_selector = Selector::parse(".holiday")?;
         let preholiday_selector = Selector::parse("td.preholiday")?;
@@ -112,15 +161,189 @@ impl ProductCalendarParser {
                 }
             }
         }
+
+        for (from, to) in self.transfers_from_document(document) {
+            for (date, transferred_from, transferred_to) in
+                [(from, None, Some(to)), (to, Some(from), None)]
+            {
+                if date.year() != self.year as i32 {
+                    continue;
+                }
+                let idx = match calendar.iter().position(|d| d.get_date() == date) {
+                    Some(idx) => idx,
+                    None => {
+                        calendar.push(Day::new(date));
+                        calendar.len() - 1
+                    }
+                };
+                let day = &mut calendar[idx];
+                if transferred_from.is_some() {
+                    day.set_transferred_from(transferred_from);
+                }
+                if transferred_to.is_some() {
+                    day.set_transferred_to(transferred_to);
+                }
+            }
+        }
         Ok(calendar)
     }
 
+    //Переносы выходных из текста постановления на странице:
+    //"с субботы 27 апреля на понедельник 29 апреля", "с 5 января 2025 г. на 31 декабря".
+    //Если формулировка не распознана, переносов просто не будет
+    fn transfers_from_document(&self, document: &Html) -> Vec<(NaiveDate, NaiveDate)> {
+        let words: Vec<String> = document
+            .root_element()
+            .text()
+            .flat_map(str::split_whitespace)
+            .map(|w| {
+                w.trim_matches(|c: char| !c.is_alphanumeric())
+                    .to_lowercase()
+            })
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let mut transfers = Vec::new();
+        for i in 0..words.len() {
+            if words[i] != "с" {
+                continue;
+            }
+            let Some((from, next)) = date_in_words(&words, i + 1) else {
+                continue;
+            };
+            let Some(on) = words
+                .iter()
+                .skip(next)
+                .take(3)
+                .position(|w| w == "на")
+                .map(|p| next + p)
+            else {
+                continue;
+            };
+            let Some((to, _)) = date_in_words(&words, on + 1) else {
+                continue;
+            };
+            //Год, указанный только у одной даты, относится и к другой:
+            //"с 5 января 2025 г. на 31 декабря" - оба дня в 2025 году
+            let year = from.2.or(to.2).unwrap_or(self.year as i32);
+            let date =
+                |(day, month, y): WordDate| NaiveDate::from_ymd_opt(y.unwrap_or(year), month, day);
+            if let (Some(from), Some(to)) = (date(from), date(to)) {
+                if !transfers.contains(&(from, to)) {
+                    transfers.push((from, to));
+                }
+            }
+        }
+        transfers
+    }
+
+    //Таблицы норм: строка заголовков с периодами ("Январь", "I квартал", "2024 г.")
+    //и строки "Календарные дни", "Рабочие дни", "Выходные и праздничные дни",
+    //"40-часовая неделя", "36-часовая неделя", "24-часовая неделя"
+    fn norms_from_document(&self, document: &Html) -> Result<Norms, Box<dyn std::error::Error>> {
+        let row_selector = Selector::parse("tr")?;
+        let cell_selector = Selector::parse("th, td")?;
+        let cell_text = |cell: ElementRef| {
+            cell.text()
+                .collect::<String>()
+                .replace('\u{a0}', " ")
+                .trim()
+                .to_string()
+        };
+
+        let mut norms = Norms {
+            year: self.year,
+            periods: Vec::new(),
+        };
+
+        for table in document.select(&Selector::parse("table")?) {
+            let mut columns: Vec<Option<NormPeriod>> = Vec::new();
+
+            for row in table.select(&row_selector) {
+                let cells: Vec<String> = row.select(&cell_selector).map(cell_text).collect();
+                let periods: Vec<Option<NormPeriod>> =
+                    cells.iter().map(|c| NormPeriod::from_label(c)).collect();
+
+                if periods.iter().filter(|p| p.is_some()).count() >= 2 {
+                    columns = periods;
+                    continue;
+                }
+                let Some(label) = cells.first().map(|c| c.to_lowercase()) else {
+                    continue;
+                };
+                if columns.is_empty() {
+                    continue;
+                }
+
+                for (period, value) in columns.iter().zip(cells.iter()) {
+                    let Some(period) = period else {
+                        continue;
+                    };
+                    let Ok(value) = value.replace(',', ".").parse::<f64>() else {
+                        continue;
+                    };
+                    let idx = match norms.periods.iter().position(|(p, _)| p == period) {
+                        Some(idx) => idx,
+                        None => {
+                            norms.periods.push((*period, PeriodNorm::default()));
+                            norms.periods.len() - 1
+                        }
+                    };
+                    let norm = &mut norms.periods[idx].1;
+
+                    if label.starts_with("календарн") {
+                        norm.calendar_days = Some(value as u16);
+                    } else if label.starts_with("рабоч") {
+                        norm.work_days = Some(value as u16);
+                    } else if label.starts_with("выходн") {
+                        norm.rest_days = Some(value as u16);
+                    } else if label.starts_with("40") {
+                        norm.hours_40 = Some(value);
+                    } else if label.starts_with("36") {
+                        norm.hours_36 = Some(value);
+                    } else if label.starts_with("24") {
+                        norm.hours_24 = Some(value);
+                    }
+                }
+            }
+        }
+        norms.periods.sort_by_key(|(p, _)| *p);
+        Ok(norms)
+    }
+
     #[inline]
     fn to_date(&self, day: String, month: u8) -> Option<NaiveDate> {
         NaiveDate::from_ymd_opt(self.year as i32, month as u32, day.parse::<u32>().ok()?)
     }
 }
 
+//День, месяц и год, если он указан
+type WordDate = (u32, u32, Option<i32>);
+
+//Дата вида "[день недели] 27 апреля [2024 г.]", начиная со слова `start`,
+//и индекс следующего за ней слова
+fn date_in_words(words: &[String], start: usize) -> Option<(WordDate, usize)> {
+    let mut i = start;
+    if words.get(i)?.parse::<u32>().is_err() {
+        i += 1;
+    }
+    let day = words.get(i)?.parse::<u32>().ok()?;
+    let month_name = words.get(i + 1)?;
+    let month = MONTHS_GENITIVE.iter().position(|m| m == month_name)? as u32 + 1;
+    i += 2;
+
+    let year = words
+        .get(i)
+        .and_then(|w| w.trim_end_matches('г').parse::<i32>().ok());
+    if year.is_some() {
+        i += 1;
+        if words.get(i).is_some_and(|w| w == "г" || w == "года") {
+            i += 1;
+        }
+    }
+    Some(((day, month, year), i))
+}
+
 mod tests {
 
     #[test]
@@ -137,12 +360,100 @@ mod tests {
         assert_eq!(parsed_calendar[0], expected_day);
     }
 
+    #[test]
+    fn test_norms_from_document() {
+        let html = r#"<table>
+            <tr><th>Период</th><th>Январь</th><th>Февраль</th><th>I квартал</th><th>2024 г.</th></tr>
+            <tr><td>Календарные дни</td><td>31</td><td>29</td><td>91</td><td>366</td></tr>
+            <tr><td>Рабочие дни</td><td>17</td><td>20</td><td>57</td><td>248</td></tr>
+            <tr><td>40-часовая неделя</td><td>136</td><td>159</td><td>454</td><td>1979</td></tr>
+            <tr><td>36-часовая неделя</td><td>122,4</td><td>143</td><td>408,4</td><td>1780,6</td></tr>
+        </table>"#;
+        let parser = super::ProductCalendarParser::new(2024);
+        let norms = parser
+            .norms_from_document(&super::Html::parse_document(html))
+            .unwrap();
+
+        let january = norms.get(super::NormPeriod::Month(1)).unwrap();
+        assert_eq!(january.work_days, Some(17));
+        assert_eq!(january.hours_36, Some(122.4));
+        assert_eq!(
+            norms.get(super::NormPeriod::Year).unwrap().hours_40,
+            Some(1979.0)
+        );
+
+        let pc = crate::pc::get_product_calendar(Some(2024)).unwrap();
+        norms.validate(&pc).unwrap();
+    }
+
+    #[test]
+    fn test_transfers_from_document() {
+        let html = r#"<table>
+            <tr><th class="month">Апрель</th></tr>
+            <tr><td class="work">27</td><td class="holiday">29</td></tr>
+        </table>
+        <p>В 2024 году переносятся выходные дни: с субботы 6&nbsp;января на пятницу 10 мая;
+        с&nbsp;воскресенья 7 января на вторник 31 декабря; с субботы 27 апреля
+        на понедельник 29 апреля.</p>"#;
+        let parser = super::ProductCalendarParser::new(2024);
+        let days = parser
+            .days_from_document(&super::Html::parse_document(html))
+            .unwrap();
+        let date = |m, d| chrono::NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let day = |m, d| {
+            days.iter()
+                .find(|day| day.get_date() == date(m, d))
+                .unwrap()
+        };
+
+        assert_eq!(day(4, 27).get_kind(), super::DayKind::Work);
+        assert_eq!(day(4, 27).get_transferred_to(), Some(date(4, 29)));
+        assert_eq!(day(4, 29).get_kind(), super::DayKind::Holiday);
+        assert_eq!(day(4, 29).get_transferred_from(), Some(date(4, 27)));
+        //Дни, которых нет в таблицах, добавляются по дню недели
+        assert_eq!(day(1, 7).get_transferred_to(), Some(date(12, 31)));
+        assert_eq!(day(12, 31).get_transferred_from(), Some(date(1, 7)));
+        assert_eq!(day(5, 10).get_transferred_from(), Some(date(1, 6)));
+    }
+
+    #[test]
+    fn test_transfers_across_years() {
+        //На странице 2024 года упоминаются переносы следующего года
+        let html = r#"<p>В 2025 году: с воскресенья 5 января 2025 г. на среду 31 декабря;
+        с субботы 28 декабря на понедельник 30 декабря 2024&nbsp;г.</p>"#;
+        let document = super::Html::parse_document(html);
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let parser = super::ProductCalendarParser::new(2024);
+        assert_eq!(
+            parser.transfers_from_document(&document),
+            [
+                (date(2025, 1, 5), date(2025, 12, 31)),
+                (date(2024, 12, 28), date(2024, 12, 30)),
+            ]
+        );
+        //Переносы другого года не попадают в дни календаря
+        let days = parser.days_from_document(&document).unwrap();
+        assert!(days.iter().all(|d| d.get_year() == 2024));
+
+        let days = super::ProductCalendarParser::new(2025)
+            .days_from_document(&document)
+            .unwrap();
+        let day = |m, d| {
+            days.iter()
+                .find(|day| day.get_date() == date(2025, m, d))
+                .unwrap()
+        };
+        assert_eq!(day(1, 5).get_transferred_to(), Some(date(2025, 12, 31)));
+        assert_eq!(day(12, 31).get_transferred_from(), Some(date(2025, 1, 5)));
+    }
+
     #[test]
     fn test_invalid_year() {
         let invalid_year = 1899_u16;
         let mut parser = super::ProductCalendarParser::new(invalid_year);
         let calendar = parser.parse_calendar();
-        assert_eq!(calendar.is_err(), true);
+        assert!(calendar.is_err());
     }
 
     #[test]
@@ -150,6 +461,6 @@ mod tests {
         let invalid_year = 2055_u16;
         let mut parser = super::ProductCalendarParser::new(invalid_year);
         let calendar = parser.parse_calendar();
-        assert_eq!(calendar.is_err(), true);
+        assert!(calendar.is_err());
     }
 }
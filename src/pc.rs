@@ -1,26 +1,74 @@
-use crate::day::{kind::DayKind, Day};
+use crate::cache;
+use crate::convention::BusinessDayConvention;
+use crate::day::{holiday::holiday_name, kind::DayKind, Day};
 use crate::errors::ProductCalendarError;
-use crate::parser::ProductCalendarParser;
-use crate::statistic::Statistic;
-use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use crate::norms::Norms;
+use crate::overlay::Overlay;
+use crate::period::{FiscalYear, Grouping, PeriodKey};
+use crate::region::Region;
+use crate::source::{default_source, CalendarSource};
+use crate::statistic::{Statistic, WeekLength};
+use chrono::{Datelike, Duration, Local, Months, NaiveDate, Weekday};
+use chrono_tz::Tz;
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::ops::Index;
-use std::sync::Mutex;
+use std::sync::{Arc, OnceLock};
 
-lazy_static! {
-    static ref CACHED_CALENDAR: Mutex<HashMap<u16, ProductCalendar>> = Mutex::new(HashMap::new());
+//Производственный календарь утверждается по московскому времени
+pub const DEFAULT_TIME_ZONE: Tz = Tz::Europe__Moscow;
+
+//Сколько лет за пределами даты подгружается в поисках нужного дня.
+//Ограничивает число запросов к источнику, если такого дня нет
+const MAX_LOADED_YEARS: i32 = 10;
+
+//Количество дней каждого вида: праздничные, предпраздничные, рабочие, выходные
+type KindCounts = [u32; 4];
+
+fn kind_index(kind: DayKind) -> usize {
+    match kind {
+        DayKind::Holiday => 0,
+        DayKind::Preholiday => 1,
+        DayKind::Work => 2,
+        DayKind::Weekend => 3,
+    }
 }
 
-//TODO: Написать трейт для календаря и сделать структуру для нескольких лет
+//TODO: Написать трейт для календаря
 #[derive(Clone, Debug)]
 pub struct ProductCalendar {
-    pub calendar: Vec<Day>,
+    //Отсортированы по дате
+    calendar: Vec<Day>,
+    source: Arc<dyn CalendarSource>,
+    //Региональные праздники и корпоративные изменения, применяются и к подгружаемым годам
+    region: Option<Region>,
+    overlay: Option<Arc<Overlay>>,
+    //Начало финансового года для разбивки по кварталам, полугодиям и годам
+    fiscal_year: FiscalYear,
+    //Часовой пояс, в котором действуют рабочий день и даты праздников
+    time_zone: Tz,
+    //Накопленные количества дней по видам: `counts[i]` - для дней `calendar[..i]`.
+    //Считаются при первом обращении и сбрасываются при изменении дней
+    counts: OnceLock<Arc<[KindCounts]>>,
+    //Есть ли дни, рассчитанные по правилам. Определяется при построении календаря,
+    //чтобы не просматривать дни при каждой проверке
+    predicted: bool,
 }
 
 impl FromIterator<Day> for ProductCalendar {
     fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
-        let calendar = iter.into_iter().collect();
-        ProductCalendar { calendar }
+        let mut calendar: Vec<Day> = iter.into_iter().collect();
+        calendar.sort_by_key(|d| d.get_date());
+        ProductCalendar {
+            predicted: calendar.iter().any(Day::is_predicted),
+            calendar,
+            source: default_source(),
+            region: None,
+            overlay: None,
+            fiscal_year: FiscalYear::default(),
+            time_zone: DEFAULT_TIME_ZONE,
+            counts: OnceLock::new(),
+        }
     }
 }
 
@@ -55,29 +103,307 @@ impl ProductCalendar {
         self.calendar.len()
     }
 
-    fn iter(&self) -> impl Iterator<Item = &Day> {
+    pub fn iter(&self) -> impl Iterator<Item = &Day> {
         self.calendar.iter()
     }
 
+    /// Дни календаря, отсортированные по дате. Заменяет бывшее публичное поле `calendar`:
+    /// прямое изменение дней нарушило бы порядок и накопленные количества.
+    pub fn days(&self) -> &[Day] {
+        &self.calendar
+    }
+
+    pub fn into_days(self) -> Vec<Day> {
+        self.calendar
+    }
+
+    //Индекс дня по дате. Для непрерывного периода вычисляется смещением
+    //от первого дня за O(1), для прореженного (например, `by_kind`) - двоичным поиском
+    fn index_of(&self, date: NaiveDate) -> Option<usize> {
+        let first = self.calendar.first()?.get_date();
+        let offset = usize::try_from((date - first).num_days()).ok()?;
+        match self.calendar.get(offset) {
+            Some(day) if day.get_date() == date => Some(offset),
+            _ => self
+                .calendar
+                .binary_search_by_key(&date, |d| d.get_date())
+                .ok(),
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.index_of(date).is_some()
+    }
+
+    //Изменяемый доступ к дням, сбрасывает накопленные количества
+    fn days_mut(&mut self) -> &mut Vec<Day> {
+        self.counts = OnceLock::new();
+        &mut self.calendar
+    }
+
+    fn counts(&self) -> &[KindCounts] {
+        self.counts.get_or_init(|| {
+            let mut acc = [0; 4];
+            std::iter::once(acc)
+                .chain(self.calendar.iter().map(move |day| {
+                    acc[kind_index(day.get_kind())] += 1;
+                    acc
+                }))
+                .collect()
+        })
+    }
+
+    //Количество дней каждого вида с `start` по `end` включительно.
+    //Если обе даты есть в календаре - разность накопленных количеств за O(1),
+    //иначе период собирается с подгрузкой соседних лет
+    fn counts_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<KindCounts, ProductCalendarError> {
+        if start > end {
+            return Err(ProductCalendarError::DateOutOfRange(start.to_string()));
+        }
+        match (self.index_of(start), self.index_of(end)) {
+            (Some(first), Some(last)) => {
+                let counts = self.counts();
+                let (before, after) = (counts[first], counts[last + 1]);
+                Ok(std::array::from_fn(|i| after[i] - before[i]))
+            }
+            _ => self.period_slice(start, end)?.counts_between(start, end),
+        }
+    }
+
+    /// Количество рабочих дней (включая предпраздничные) с `start` по `end` включительно.
+    pub fn work_days_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<u32, ProductCalendarError> {
+        let counts = self.counts_between(start, end)?;
+        Ok(counts[kind_index(DayKind::Work)] + counts[kind_index(DayKind::Preholiday)])
+    }
+
+    /// Норма рабочих часов для недели `week` с `start` по `end` включительно.
+    pub fn work_hours_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        week: WeekLength,
+    ) -> Result<f64, ProductCalendarError> {
+        Ok(self.statistic_between(start, end)?.work_hours_norm(week))
+    }
+
+    /// Статистика по дням с `start` по `end` включительно.
+    pub fn statistic_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Statistic, ProductCalendarError> {
+        let counts = self.counts_between(start, end)?;
+        Ok(Statistic {
+            holidays: counts[kind_index(DayKind::Holiday)] as u16,
+            work_days: counts[kind_index(DayKind::Work)] as u16,
+            weekends: counts[kind_index(DayKind::Weekend)] as u16,
+            preholidays: counts[kind_index(DayKind::Preholiday)] as u16,
+        })
+    }
+
+    //Новый календарь с тем же источником данных
+    fn with_days(&self, calendar: Vec<Day>) -> Self {
+        Self {
+            predicted: calendar.iter().any(Day::is_predicted),
+            calendar,
+            source: self.source.clone(),
+            region: self.region,
+            overlay: self.overlay.clone(),
+            fiscal_year: self.fiscal_year,
+            time_zone: self.time_zone,
+            counts: OnceLock::new(),
+        }
+    }
+
+    /// Содержит ли календарь дни, рассчитанные по правилам, а не официальные данные.
+    pub fn is_predicted(&self) -> bool {
+        self.predicted
+    }
+
+    /// Официальные нормы рабочего времени за год из источника календаря.
+    /// Нормы кэшируются вместе с днями календаря за этот год.
+    pub fn official_norms(&self, year: u16) -> Result<Option<Norms>, Box<dyn std::error::Error>> {
+        cache::load_norms(year, self.source.clone())
+    }
+
+    /// Сверяет дни календаря с официальными нормами за каждый год периода.
+    /// Годы, для которых источник не публикует нормы, пропускаются.
+    pub fn validate_norms(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let (first, last) = match (self.first(), self.last()) {
+            (Some(first), Some(last)) => (first.get_year(), last.get_year()),
+            _ => return Ok(()),
+        };
+        for year in first..=last {
+            let year = u16::try_from(year)?;
+            if let Some(norms) = self.official_norms(year)? {
+                norms.validate(self)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn source(&self) -> Arc<dyn CalendarSource> {
+        self.source.clone()
+    }
+
+    pub fn overlay(&self) -> Option<&Overlay> {
+        self.overlay.as_deref()
+    }
+
+    /// Календарь с корпоративными изменениями поверх текущего.
+    /// Изменения учитываются в статистике и во всех вычислениях по рабочим дням,
+    /// в том числе для подгружаемых соседних лет.
+    pub fn with_overlay(&self, overlay: &Overlay) -> Self {
+        let mut combined = self.overlay.as_deref().cloned().unwrap_or_default();
+        combined.extend(overlay);
+
+        let mut pc = self.clone();
+        overlay.apply(pc.days_mut());
+        pc.overlay = Some(Arc::new(combined));
+        pc
+    }
+
+    pub fn fiscal_year(&self) -> FiscalYear {
+        self.fiscal_year
+    }
+
+    /// Календарь, в котором кварталы, полугодия и годы отсчитываются от начала
+    /// финансового года `fiscal_year`.
+    pub fn with_fiscal_year(&self, fiscal_year: FiscalYear) -> Self {
+        let mut pc = self.clone();
+        pc.fiscal_year = fiscal_year;
+        pc
+    }
+
+    pub fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+
+    /// Календарь, рабочее время которого отсчитывается по местному времени `time_zone`.
+    pub fn with_time_zone(&self, time_zone: Tz) -> Self {
+        let mut pc = self.clone();
+        pc.time_zone = time_zone;
+        pc
+    }
+
+    pub fn region(&self) -> Option<Region> {
+        self.region
+    }
+
+    /// Календарь с региональными праздниками и сокращенными днями перед ними.
+    /// Корпоративные изменения сохраняют приоритет над региональными праздниками.
+    pub fn with_region(&self, region: Region) -> Self {
+        let mut pc = self.clone();
+        region.apply(pc.days_mut());
+        if let Some(overlay) = &self.overlay {
+            overlay.apply(pc.days_mut());
+        }
+        pc.region = Some(region);
+        pc
+    }
+
+    //Календарь за год из того же источника и с теми же изменениями, что и текущий.
+    //Без изменений календарь берется из кэша без копирования дней
+    fn load_year(&self, year: i32) -> Option<Arc<ProductCalendar>> {
+        let year = u16::try_from(year).ok()?;
+        let cached = cache::load(year, self.source.clone()).ok()?;
+        if self.region.is_none() && self.overlay.is_none() {
+            return Some(cached);
+        }
+        let mut pc = Arc::unwrap_or_clone(cached);
+        if let Some(region) = self.region {
+            pc = pc.with_region(region);
+        }
+        if let Some(overlay) = &self.overlay {
+            pc = pc.with_overlay(overlay);
+        }
+        Some(Arc::new(pc))
+    }
+
+    //Дни после `date`, при необходимости подгружаются следующие годы
+    fn following(&self, date: NaiveDate) -> FollowingDays<'_> {
+        FollowingDays {
+            pc: self,
+            after: date,
+            year: date.year(),
+            last_year: date.year() + MAX_LOADED_YEARS,
+            loaded: None,
+            idx: 0,
+        }
+    }
+
+    //Дни до `date` в обратном порядке, при необходимости подгружаются предыдущие годы
+    fn preceding(&self, date: NaiveDate) -> PrecedingDays<'_> {
+        PrecedingDays {
+            pc: self,
+            before: date,
+            year: date.year(),
+            first_year: date.year() - MAX_LOADED_YEARS,
+            loaded: None,
+            idx: 0,
+        }
+    }
+
+    //Дни начиная с `date`: сначала из текущего календаря, затем из следующих лет
+    pub(crate) fn forward_from(&self, date: NaiveDate) -> Box<dyn Iterator<Item = Day> + '_> {
+        match (self.index_of(date), self.last()) {
+            (Some(idx), Some(last)) => Box::new(
+                self.calendar[idx..]
+                    .iter()
+                    .cloned()
+                    .chain(self.following(last.get_date())),
+            ),
+            _ => Box::new(
+                date.pred_opt()
+                    .map(|prev| self.following(prev))
+                    .into_iter()
+                    .flatten(),
+            ),
+        }
+    }
+
+    //Дни начиная с `date` в обратном порядке, затем из предыдущих лет
+    pub(crate) fn backward_from(&self, date: NaiveDate) -> Box<dyn Iterator<Item = Day> + '_> {
+        match (self.index_of(date), self.first()) {
+            (Some(idx), Some(first)) => Box::new(
+                self.calendar[..=idx]
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .chain(self.preceding(first.get_date())),
+            ),
+            _ => Box::new(
+                date.succ_opt()
+                    .map(|next| self.preceding(next))
+                    .into_iter()
+                    .flatten(),
+            ),
+        }
+    }
+
     pub fn after_nth_weeks(
         &self,
         date: NaiveDate,
         weeks: usize,
     ) -> Result<Day, ProductCalendarError> {
-        let start_idx = self
-            .iter()
-            .position(|d| d.get_date() == date)
-            .ok_or_else(|| ProductCalendarError::DateOutOfRange(date.to_string()))?;
-
-        let end_idx = start_idx
-            .checked_add(weeks * 7)
-            .ok_or_else(|| ProductCalendarError::ExceedMaxDaysError(start_idx + weeks * 7))?;
-
-        if end_idx >= self.calendar.len() {
-            return Err(ProductCalendarError::ExceedMaxDaysError(end_idx));
+        let mut days = self.forward_from(date).peekable();
+        if days.peek().is_none() {
+            return Err(ProductCalendarError::DateOutOfRange(date.to_string()));
         }
 
-        Ok(self.calendar[end_idx].clone())
+        let offset = weeks
+            .checked_mul(7)
+            .ok_or(ProductCalendarError::ExceedMaxDaysError(usize::MAX))?;
+        days.nth(offset)
+            .ok_or(ProductCalendarError::ExceedMaxDaysError(offset))
     }
 
     pub fn last(&self) -> Option<&Day> {
@@ -89,68 +415,49 @@ impl ProductCalendar {
     }
 
     pub fn extend_forward(&mut self, days: usize) -> Result<(), ProductCalendarError> {
-        let first_day = self.first().ok_or(ProductCalendarError::ShiftError(
-            "Календарь пуст".to_string(),
-        ))?;
         //Последний день текущего календаря
         let last_day = self.last().ok_or(ProductCalendarError::ShiftError(
             "Календарь пуст".to_string(),
         ))?;
-        //
-        //Текущий год
-        let year = last_day.get_date().year() as u16;
-
-        //Получаем календарь за текущий год
-        let cached_calendar = CACHED_CALENDAR.lock().unwrap();
-        let cached_pc = cached_calendar
-            .get(&year)
-            .ok_or(ProductCalendarError::CantFindDay(
-                "Календарь не найден".to_string(),
-            ))?;
-
-        let target_idx = last_day.ordinal() as usize + days;
-        if target_idx > cached_pc.total_days() {
+
+        let extension: Vec<Day> = self.following(last_day.get_date()).take(days).collect();
+        if extension.len() < days {
             return Err(ProductCalendarError::ShiftError(format!(
                 "Сдвиг превышает {} дней.",
-                cached_pc.total_days(),
+                extension.len(),
             )));
         }
-        self.calendar = cached_pc.calendar[first_day.ordinal() as usize..target_idx].to_vec();
+        self.predicted |= extension.iter().any(Day::is_predicted);
+        self.days_mut().extend(extension);
         Ok(())
     }
 
     pub fn extend_backward(&mut self, days: usize) -> Result<(), ProductCalendarError> {
+        //Первый день текущего календаря
         let first_day = self.first().ok_or(ProductCalendarError::ShiftError(
             "Календарь пуст".to_string(),
         ))?;
-        //Последний день текущего календаря
-        let last_day = self.last().ok_or(ProductCalendarError::ShiftError(
-            "Календарь пуст".to_string(),
-        ))?;
-        //
-        //Текущий год
-        let year = last_day.get_date().year() as u16;
-
-        //Получаем календарь за текущий год
-        let cached_calendar = CACHED_CALENDAR.lock().unwrap();
-        let cached_pc = cached_calendar
-            .get(&year)
-            .ok_or(ProductCalendarError::CantFindDay(
-                "Календарь не найден".to_string(),
-            ))?;
-
-        let target_idx = first_day.ordinal() as usize - days - 1;
-        if target_idx < 1 {
+
+        let mut extension: Vec<Day> = self.preceding(first_day.get_date()).take(days).collect();
+        if extension.len() < days {
             return Err(ProductCalendarError::ShiftError(format!(
                 "Сдвиг превышает {} дней.",
-                cached_pc.total_days(),
+                extension.len(),
             )));
         }
-        self.calendar = cached_pc.calendar[target_idx..last_day.ordinal() as usize].to_vec();
+        extension.reverse();
+        self.predicted |= extension.iter().any(Day::is_predicted);
+        extension.append(self.days_mut());
+        self.calendar = extension;
         Ok(())
     }
 
     pub fn new(year: u16) -> ProductCalendar {
+        Self::with_source(year, default_source())
+    }
+
+    //Календарь обычной пятидневки, уточняемый данными из `source`
+    pub fn with_source(year: u16, source: Arc<dyn CalendarSource>) -> ProductCalendar {
         let start_date = NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap();
         let end_date = NaiveDate::from_ymd_opt(year as i32, 12, 31).unwrap();
 
@@ -168,11 +475,37 @@ impl ProductCalendar {
             })
             .collect();
 
-        ProductCalendar { calendar }
+        ProductCalendar {
+            calendar,
+            source,
+            region: None,
+            overlay: None,
+            fiscal_year: FiscalYear::default(),
+            time_zone: DEFAULT_TIME_ZONE,
+            counts: OnceLock::new(),
+            predicted: false,
+        }
+    }
+
+    //Календарь за год по данным, полученным из источника
+    pub(crate) fn from_source_data(
+        year: u16,
+        source: Arc<dyn CalendarSource>,
+        mut source_data: Vec<Day>,
+    ) -> ProductCalendar {
+        let mut prod_cal = ProductCalendar::with_source(year, source);
+        prod_cal.predicted = source_data.iter().any(Day::is_predicted);
+        prod_cal.merge(&mut source_data);
+        for day in prod_cal.calendar.iter_mut() {
+            if day.get_name().is_none() {
+                day.set_name(holiday_name(day.get_date()).map(str::to_owned));
+            }
+        }
+        prod_cal
     }
 
     fn merge(&mut self, consultant_data: &mut Vec<Day>) {
-        self.calendar.retain(|d| {
+        self.days_mut().retain(|d| {
             !consultant_data
                 .iter()
                 .any(|cd| d.get_date() == cd.get_date())
@@ -186,22 +519,16 @@ impl ProductCalendar {
         date: NaiveDate,
         days: usize,
     ) -> Result<Self, ProductCalendarError> {
-        let start_idx = self
-            .iter()
-            .position(|d| d.get_date() == date)
-            .ok_or_else(|| ProductCalendarError::DateOutOfRange(date.to_string()))?;
-
-        let end_idx = start_idx
-            .checked_add(days)
-            .ok_or_else(|| ProductCalendarError::ExceedMaxDaysError(start_idx + days))?;
-
-        if end_idx > self.calendar.len() {
-            return Err(ProductCalendarError::ExceedMaxDaysError(end_idx));
+        let mut period = self.forward_from(date).peekable();
+        if period.peek().is_none() {
+            return Err(ProductCalendarError::DateOutOfRange(date.to_string()));
         }
 
-        Ok(Self {
-            calendar: self.calendar[start_idx..end_idx].to_vec(),
-        })
+        let calendar: Vec<Day> = period.take(days).collect();
+        if calendar.len() < days {
+            return Err(ProductCalendarError::ExceedMaxDaysError(days));
+        }
+        Ok(self.with_days(calendar))
     }
 
     pub fn period_by_number_of_work_days(
@@ -209,41 +536,98 @@ impl ProductCalendar {
         date: NaiveDate,
         mut work_days: usize,
     ) -> Result<Self, ProductCalendarError> {
-        let start_idx = self.iter().position(|d| d.get_date() == date);
-
-        if let Some(start_idx) = start_idx {
-            let mut end_idx = start_idx;
-            for day in self.calendar[start_idx..].iter() {
-                if day.get_kind() == DayKind::Work || day.get_kind() == DayKind::Preholiday {
-                    if work_days == 0 {
-                        break;
-                    }
-                    work_days -= 1;
+        let mut period = self.forward_from(date).peekable();
+        if period.peek().is_none() {
+            return Err(ProductCalendarError::DateOutOfRange(date.to_string()));
+        }
+
+        let requested = work_days;
+        let mut calendar = Vec::new();
+        for day in period {
+            if matches!(day.get_kind(), DayKind::Work | DayKind::Preholiday) {
+                if work_days == 0 {
+                    return Ok(self.with_days(calendar));
                 }
-                end_idx += 1;
-            }
-            if end_idx > self.calendar.len() {
-                return Err(ProductCalendarError::ExceedMaxDaysError(end_idx));
+                work_days -= 1;
             }
-            Ok(Self {
-                calendar: self.calendar[start_idx..end_idx].to_vec(),
-            })
-        } else {
-            Err(ProductCalendarError::DateOutOfRange(date.to_string()))
+            calendar.push(day);
         }
+        Err(ProductCalendarError::ExceedMaxDaysError(requested))
     }
 
     pub fn next_work_day(&self, cur_day: NaiveDate) -> Result<Day, ProductCalendarError> {
-        let start_idx = self.iter().position(|d| d.get_date() == cur_day);
+        let mut days = self.forward_from(cur_day);
+        days.next()
+            .ok_or_else(|| ProductCalendarError::DateOutOfRange(cur_day.to_string()))?;
+        days.find(|d| matches!(d.get_kind(), DayKind::Work | DayKind::Preholiday))
+            .ok_or_else(|| ProductCalendarError::DateOutOfRange(cur_day.to_string()))
+    }
 
-        if let Some(start_idx) = start_idx {
-            for d in self.calendar.iter().skip(start_idx + 1) {
-                if matches!(d.get_kind(), DayKind::Work | DayKind::Preholiday) {
-                    return Ok(d.clone());
+    pub fn previous_work_day(&self, cur_day: NaiveDate) -> Result<Day, ProductCalendarError> {
+        let mut days = self.backward_from(cur_day);
+        days.next()
+            .ok_or_else(|| ProductCalendarError::DateOutOfRange(cur_day.to_string()))?;
+        days.find(|d| matches!(d.get_kind(), DayKind::Work | DayKind::Preholiday))
+            .ok_or_else(|| ProductCalendarError::DateOutOfRange(cur_day.to_string()))
+    }
+
+    //День по дате, в том числе за пределами календаря
+    pub(crate) fn day_at(&self, date: NaiveDate) -> Result<Day, ProductCalendarError> {
+        self.forward_from(date)
+            .next()
+            .ok_or_else(|| ProductCalendarError::DateOutOfRange(date.to_string()))
+    }
+
+    /// Переносит дату, выпавшую на выходной или праздник, на рабочий день по правилу `convention`.
+    /// Рабочие и предпраздничные дни возвращаются без изменений.
+    pub fn adjust(
+        &self,
+        date: NaiveDate,
+        convention: BusinessDayConvention,
+    ) -> Result<Day, ProductCalendarError> {
+        let current = self.day_at(date)?;
+        if matches!(current.get_kind(), DayKind::Work | DayKind::Preholiday) {
+            return Ok(current);
+        }
+
+        match convention {
+            BusinessDayConvention::Following => self.next_work_day(date),
+            BusinessDayConvention::Preceding => self.previous_work_day(date),
+            BusinessDayConvention::ModifiedFollowing => {
+                let next = self.next_work_day(date)?;
+                if next.get_date().month() == date.month() {
+                    Ok(next)
+                } else {
+                    self.previous_work_day(date)
                 }
             }
         }
-        Err(ProductCalendarError::DateOutOfRange(cur_day.to_string()))
+    }
+
+    /// День, отстоящий от `date` на `work_days` рабочих дней: вперед при положительном
+    /// значении, назад при отрицательном. Сама дата не учитывается, при нуле возвращается она же.
+    pub fn add_work_days(
+        &self,
+        date: NaiveDate,
+        work_days: i64,
+    ) -> Result<Day, ProductCalendarError> {
+        let mut days = if work_days < 0 {
+            self.backward_from(date)
+        } else {
+            self.forward_from(date)
+        };
+        let current = days
+            .next()
+            .ok_or_else(|| ProductCalendarError::DateOutOfRange(date.to_string()))?;
+        if work_days == 0 {
+            return Ok(current);
+        }
+
+        let offset = usize::try_from(work_days.unsigned_abs())
+            .map_err(|_| ProductCalendarError::ExceedMaxDaysError(usize::MAX))?;
+        days.filter(|d| matches!(d.get_kind(), DayKind::Work | DayKind::Preholiday))
+            .nth(offset - 1)
+            .ok_or(ProductCalendarError::ExceedMaxDaysError(offset))
     }
 
     pub fn period_slice(
@@ -251,29 +635,18 @@ impl ProductCalendar {
         start: NaiveDate,
         end: NaiveDate,
     ) -> Result<Self, ProductCalendarError> {
-        let mut start_idx = None;
-        let mut end_idx = None;
-
-        for (i, day) in self.iter().enumerate() {
-            if day.get_date() == start {
-                start_idx = Some(i);
-            }
-            if day.get_date() == end {
-                end_idx = Some(i);
-            }
-            if start_idx.is_some() && end_idx.is_some() {
-                break;
-            }
+        if start > end {
+            return Err(ProductCalendarError::DateOutOfRange(start.to_string()));
         }
 
-        match (start_idx, end_idx) {
-            (Some(start_idx), Some(end_idx)) => {
-                if start_idx > end_idx {
-                    return Err(ProductCalendarError::DateOutOfRange(start.to_string()));
-                }
-                Ok(Self {
-                    calendar: self.calendar[start_idx..=end_idx].to_vec(),
-                })
+        let calendar: Vec<Day> = self
+            .forward_from(start)
+            .take_while(|d| d.get_date() <= end)
+            .collect();
+
+        match (calendar.first(), calendar.last()) {
+            (Some(first), Some(last)) if first.get_date() == start && last.get_date() == end => {
+                Ok(self.with_days(calendar))
             }
             _ => Err(ProductCalendarError::DateOutOfRange(format!(
                 "{} - {}",
@@ -282,30 +655,166 @@ impl ProductCalendar {
         }
     }
 
+    //Дни, удовлетворяющие условию, с сохранением источника и изменений
+    fn filter_days(&self, predicate: impl Fn(&Day) -> bool) -> Self {
+        self.with_days(
+            self.calendar
+                .iter()
+                .filter(|day| predicate(day))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    //Разбивает календарь на группы дней с одинаковым ключом
+    //в порядке первого появления ключа
+    fn group_days<K: Eq + Hash + Clone>(&self, key: impl Fn(&Day) -> K) -> Vec<(K, Self)> {
+        let mut index: HashMap<K, usize> = HashMap::new();
+        let mut groups: Vec<(K, Vec<Day>)> = Vec::new();
+        for day in &self.calendar {
+            let day_key = key(day);
+            match index.get(&day_key) {
+                Some(&idx) => groups[idx].1.push(day.clone()),
+                None => {
+                    index.insert(day_key.clone(), groups.len());
+                    groups.push((day_key, vec![day.clone()]));
+                }
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(key, days)| (key, self.with_days(days)))
+            .collect()
+    }
+
+    /// Календарь, разбитый на месяцы, кварталы, полугодия или недели, со статистикой
+    /// каждой группы. Группы упорядочены по датам, крайние группы периода могут быть неполными.
+    pub fn group_by(&self, grouping: Grouping) -> Vec<(PeriodKey, (Self, Statistic))> {
+        self.group_by_key(|day| grouping.key(day.get_date(), self.fiscal_year))
+    }
+
+    /// Календарь, разбитый на произвольные группы по названию, которое `key` возвращает
+    /// для каждого дня, со статистикой каждой группы. Группы упорядочены по первому дню.
+    pub fn group_by_custom(
+        &self,
+        key: impl Fn(&Day) -> String,
+    ) -> Vec<(PeriodKey, (Self, Statistic))> {
+        self.group_by_key(|day| PeriodKey::Custom(key(day)))
+    }
+
+    fn group_by_key(&self, key: impl Fn(&Day) -> PeriodKey) -> Vec<(PeriodKey, (Self, Statistic))> {
+        self.group_days(key)
+            .into_iter()
+            .map(|(key, pc)| {
+                let statistic = pc.statistic();
+                (key, (pc, statistic))
+            })
+            .collect()
+    }
+
+    /// Дни календаря, приходящиеся на квартал `quarter`. Границы квартала определяются
+    /// по датам дней, поэтому календарь может начинаться с любой даты и охватывать
+    /// несколько лет - в этом случае в результат попадает этот квартал каждого года.
     pub fn extract_dates_in_quarter(&self, quarter: u8) -> Result<Self, ProductCalendarError> {
-        let first_quarter_len = if self.calendar.len() == 366 { 90 } else { 89 };
-        let (start_idx, end_idx) = match quarter {
-            1 => (0, first_quarter_len + 1),
-            2 => (first_quarter_len + 1, first_quarter_len + 92),
-            3 => (first_quarter_len + 92, first_quarter_len + 184),
-            4 => (first_quarter_len + 184, self.calendar.len()),
-            _ => return Err(ProductCalendarError::InvalidQuarter(quarter)),
+        if !(1..=4).contains(&quarter) {
+            return Err(ProductCalendarError::InvalidQuarter(quarter));
+        }
+        Ok(self.filter_days(|day| self.fiscal_year.quarter_of(day.get_date()) == quarter))
+    }
+
+    /// Дни календаря, приходящиеся на полугодие `half` (1 или 2).
+    pub fn extract_dates_in_half_year(&self, half: u8) -> Result<Self, ProductCalendarError> {
+        if !(1..=2).contains(&half) {
+            return Err(ProductCalendarError::InvalidHalfYear(half));
+        }
+        Ok(self.filter_days(|day| self.fiscal_year.half_year_of(day.get_date()) == half))
+    }
+
+    /// Календарь, разбитый на кварталы, со статистикой каждого квартала в порядке следования.
+    /// Крайние кварталы периода могут быть неполными.
+    pub fn by_quarters(&self) -> Vec<(PeriodKey, (Self, Statistic))> {
+        self.group_by(Grouping::Quarter)
+    }
+
+    /// Календарь, разбитый на полугодия, со статистикой каждого полугодия в порядке следования.
+    pub fn by_half_years(&self) -> Vec<(PeriodKey, (Self, Statistic))> {
+        self.group_by(Grouping::HalfYear)
+    }
+
+    /// Календарь, разбитый на недели по ISO 8601 (с понедельника по воскресенье),
+    /// со статистикой каждой недели. Первая и последняя недели периода могут быть неполными.
+    pub fn by_weeks(&self) -> Vec<(PeriodKey, (Self, Statistic))> {
+        self.group_by(Grouping::Week)
+    }
+
+    /// Дни календаря, приходящиеся на месяц `month`. Для календаря за несколько лет
+    /// в результат попадает этот месяц каждого года периода.
+    pub fn extract_dates_in_month(&self, month: u8) -> Result<Self, ProductCalendarError> {
+        if !(1..=12).contains(&month) {
+            return Err(ProductCalendarError::InvalidMonth(month));
+        }
+        Ok(self.filter_days(|day| day.get_date().month() == month as u32))
+    }
+
+    //Первое число месяца
+    fn month_start(year: i32, month: u8) -> Result<NaiveDate, ProductCalendarError> {
+        if !(1..=12).contains(&month) {
+            return Err(ProductCalendarError::InvalidMonth(month));
+        }
+        NaiveDate::from_ymd_opt(year, month as u32, 1)
+            .ok_or_else(|| ProductCalendarError::InvalidYear(year.to_string()))
+    }
+
+    /// `n`-й рабочий день месяца, начиная с 1.
+    pub fn nth_work_day_of_month(
+        &self,
+        year: i32,
+        month: u8,
+        n: usize,
+    ) -> Result<Day, ProductCalendarError> {
+        let start = Self::month_start(year, month)?;
+        let not_found = || {
+            ProductCalendarError::CantFindDay(format!(
+                "В месяце {:02}.{} нет {}-го рабочего дня",
+                month, year, n
+            ))
         };
+        if n == 0 {
+            return Err(not_found());
+        }
 
-        Ok(Self {
-            calendar: self.calendar[start_idx..end_idx].to_vec(),
-        })
+        let mut days = self.forward_from(start).peekable();
+        if days.peek().is_none() {
+            return Err(ProductCalendarError::DateOutOfRange(start.to_string()));
+        }
+        days.take_while(|d| d.get_date().month() == month as u32)
+            .filter(|d| matches!(d.get_kind(), DayKind::Work | DayKind::Preholiday))
+            .nth(n - 1)
+            .ok_or_else(not_found)
+    }
+
+    pub fn first_work_day_of_month(
+        &self,
+        year: i32,
+        month: u8,
+    ) -> Result<Day, ProductCalendarError> {
+        self.nth_work_day_of_month(year, month, 1)
+    }
+
+    pub fn last_work_day_of_month(
+        &self,
+        year: i32,
+        month: u8,
+    ) -> Result<Day, ProductCalendarError> {
+        let end = Self::month_start(year, month)?
+            .checked_add_months(Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .ok_or_else(|| ProductCalendarError::InvalidYear(year.to_string()))?;
+        self.adjust(end, BusinessDayConvention::Preceding)
     }
 
     pub fn by_kind(&self, kind: DayKind) -> Self {
-        Self {
-            calendar: self
-                .calendar
-                .iter()
-                .filter(|day| day.get_kind() == kind)
-                .cloned()
-                .collect(),
-        }
+        self.filter_days(|day| day.get_kind() == kind)
     }
 
     pub fn statistic(&self) -> Statistic {
@@ -322,29 +831,113 @@ impl ProductCalendar {
     }
 
     pub fn info_by_date(&self, date: NaiveDate) -> Option<Day> {
-        self.iter().find(|day| date == day.get_date()).cloned()
+        self.index_of(date).map(|idx| self.calendar[idx].clone())
     }
 }
 
-pub fn get_product_calendar(
-    year: Option<u16>,
-) -> Result<ProductCalendar, Box<dyn std::error::Error>> {
-    let year = year.unwrap_or(Local::now().year() as u16);
+struct FollowingDays<'a> {
+    pc: &'a ProductCalendar,
+    after: NaiveDate,
+    year: i32,
+    last_year: i32,
+    //Подгруженный год и индекс следующего дня в нем
+    loaded: Option<Arc<ProductCalendar>>,
+    idx: usize,
+}
 
-    let mut parser = ProductCalendarParser::new(year);
+impl Iterator for FollowingDays<'_> {
+    type Item = Day;
 
-    let mut cached = CACHED_CALENDAR.lock().unwrap();
+    fn next(&mut self) -> Option<Day> {
+        loop {
+            if let Some(day) = self
+                .loaded
+                .as_ref()
+                .and_then(|pc| pc.calendar.get(self.idx))
+            {
+                self.idx += 1;
+                return Some(day.clone());
+            }
+            if self.year > self.last_year {
+                return None;
+            }
+            let pc = self.pc.load_year(self.year)?;
+            let after = self.after;
+            self.idx = pc.calendar.partition_point(|d| d.get_date() <= after);
+            self.loaded = Some(pc);
+            self.year += 1;
+        }
+    }
+}
 
-    if let Some(cached_cal) = cached.get(&year) {
-        return Ok(cached_cal.clone());
+struct PrecedingDays<'a> {
+    pc: &'a ProductCalendar,
+    before: NaiveDate,
+    year: i32,
+    first_year: i32,
+    //Подгруженный год и количество еще не выданных дней в нем
+    loaded: Option<Arc<ProductCalendar>>,
+    idx: usize,
+}
+
+impl Iterator for PrecedingDays<'_> {
+    type Item = Day;
+
+    fn next(&mut self) -> Option<Day> {
+        loop {
+            if let Some(pc) = self.loaded.as_ref().filter(|_| self.idx > 0) {
+                self.idx -= 1;
+                return Some(pc.calendar[self.idx].clone());
+            }
+            if self.year < self.first_year {
+                return None;
+            }
+            let pc = self.pc.load_year(self.year)?;
+            let before = self.before;
+            self.idx = pc.calendar.partition_point(|d| d.get_date() < before);
+            self.loaded = Some(pc);
+            self.year -= 1;
+        }
     }
+}
 
-    let mut consultant_data = parser.parse_calendar()?;
-    let mut prod_cal = ProductCalendar::new(year);
-    prod_cal.merge(&mut consultant_data);
-    cached.insert(year, prod_cal.clone());
+/// Календарь за произвольный период, в том числе охватывающий несколько лет.
+pub fn get_product_calendar_range(
+    start: NaiveDate,
+    end: NaiveDate,
+    source: Arc<dyn CalendarSource>,
+) -> Result<ProductCalendar, Box<dyn std::error::Error>> {
+    let year = u16::try_from(start.year())
+        .map_err(|_| ProductCalendarError::InvalidYear(start.year().to_string()))?;
+    let pc = get_product_calendar_from(Some(year), source)?;
+    Ok(pc.period_slice(start, end)?)
+}
 
-    Ok(prod_cal)
+/// Календарь за финансовый год `year`, начинающийся с первого числа месяца
+/// `fiscal_year.start_month()`. Данные берутся из кэша по календарным годам.
+pub fn get_product_calendar_fiscal(
+    year: u16,
+    fiscal_year: FiscalYear,
+    source: Arc<dyn CalendarSource>,
+) -> Result<ProductCalendar, Box<dyn std::error::Error>> {
+    let (start, end) = fiscal_year
+        .bounds(year as i32)
+        .ok_or_else(|| ProductCalendarError::InvalidYear(year.to_string()))?;
+    Ok(get_product_calendar_range(start, end, source)?.with_fiscal_year(fiscal_year))
+}
+
+pub fn get_product_calendar(
+    year: Option<u16>,
+) -> Result<ProductCalendar, Box<dyn std::error::Error>> {
+    get_product_calendar_from(year, default_source())
+}
+
+pub fn get_product_calendar_from(
+    year: Option<u16>,
+    source: Arc<dyn CalendarSource>,
+) -> Result<ProductCalendar, Box<dyn std::error::Error>> {
+    let year = year.unwrap_or(Local::now().year() as u16);
+    Ok(Arc::unwrap_or_clone(cache::load(year, source)?))
 }
 
 #[cfg(test)]
@@ -365,15 +958,12 @@ mod tests {
     }
 
     fn _create_period() -> ProductCalendar {
-        let pc = {
-            let _pc = get_product_calendar(Some(2024));
-            let start = NaiveDate::from_ymd_opt(2024, 09, 01).unwrap();
-            let end = NaiveDate::from_ymd_opt(2024, 09, 30).unwrap();
-            _pc.expect("Не удалось получить календарь...")
-                .period_slice(start, end)
-                .unwrap()
-        };
-        pc
+        let _pc = get_product_calendar(Some(2024));
+        let start = NaiveDate::from_ymd_opt(2024, 9, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        _pc.expect("Не удалось получить календарь...")
+            .period_slice(start, end)
+            .unwrap()
     }
 
     #[test]
@@ -445,6 +1035,17 @@ mod tests {
         assert_eq!(pc.first(), Some(&expected_day));
     }
 
+    #[test]
+    fn test_days() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        assert_eq!(pc.days().len(), 366);
+        assert_eq!(pc.days().first(), pc.first());
+
+        let days = pc.clone().into_days();
+        assert_eq!(days.len(), 366);
+        assert_eq!(ProductCalendar::from_iter(days).days(), pc.days());
+    }
+
     #[test]
     fn test_info_by_day() {
         let pc = get_product_calendar_for_year(Some(2024));
@@ -482,4 +1083,458 @@ mod tests {
                 .calendar,
         );
     }
+
+    #[test]
+    fn test_work_days_across_new_year() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        let next = pc
+            .next_work_day(NaiveDate::from_ymd_opt(2024, 12, 28).unwrap())
+            .unwrap();
+        assert_eq!(
+            next.get_date(),
+            NaiveDate::from_ymd_opt(2025, 1, 9).unwrap()
+        );
+
+        let period = pc
+            .period_by_number_of_work_days(NaiveDate::from_ymd_opt(2024, 12, 27).unwrap(), 3)
+            .unwrap();
+        assert_eq!(period.total_days(), 14);
+        assert_eq!(
+            period.last().unwrap().get_date(),
+            NaiveDate::from_ymd_opt(2025, 1, 9).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_range_across_years() {
+        let pc = get_product_calendar_range(
+            NaiveDate::from_ymd_opt(2023, 12, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            default_source(),
+        )
+        .unwrap();
+        assert_eq!(pc.total_days(), 31 + 366 + 31);
+
+        let mut period = _create_period();
+        period.extend_forward(120).unwrap();
+        assert_eq!(
+            period.last().unwrap().get_date(),
+            NaiveDate::from_ymd_opt(2025, 1, 28).unwrap()
+        );
+        assert_eq!(
+            pc.after_nth_weeks(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(), 1)
+                .unwrap()
+                .get_date(),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut overlay = Overlay::new();
+        overlay.add(
+            NaiveDate::from_ymd_opt(2024, 5, 13).unwrap(),
+            DayKind::Holiday,
+            "День компании",
+        );
+        overlay.add(
+            NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            DayKind::Work,
+            "Инвентаризация",
+        );
+        overlay.add(
+            NaiveDate::from_ymd_opt(2025, 1, 9).unwrap(),
+            DayKind::Holiday,
+            "Перенос отпуска",
+        );
+        overlay.add(
+            NaiveDate::from_ymd_opt(2024, 5, 9).unwrap(),
+            DayKind::Holiday,
+            "Возложение цветов",
+        );
+
+        let pc = get_product_calendar_for_year(Some(2024)).with_overlay(&overlay);
+        //Официальное название праздника сохраняется
+        assert_eq!(
+            pc.day_at(NaiveDate::from_ymd_opt(2024, 5, 9).unwrap())
+                .unwrap()
+                .get_name(),
+            Some("День Победы")
+        );
+        assert_eq!(
+            pc.day_at(NaiveDate::from_ymd_opt(2024, 5, 13).unwrap())
+                .unwrap()
+                .get_name(),
+            Some("День компании")
+        );
+        let statistic = pc.statistic();
+        assert_eq!(statistic.holidays, 18);
+        assert_eq!(statistic.work_days, 243);
+        assert_eq!(statistic.weekends, 100);
+        assert_eq!(
+            pc.next_work_day(NaiveDate::from_ymd_opt(2024, 5, 10).unwrap())
+                .unwrap()
+                .get_date(),
+            NaiveDate::from_ymd_opt(2024, 5, 14).unwrap()
+        );
+        //Изменения применяются и к подгруженному следующему году
+        assert_eq!(
+            pc.next_work_day(NaiveDate::from_ymd_opt(2024, 12, 28).unwrap())
+                .unwrap()
+                .get_date(),
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()
+        );
+
+        let path = std::env::temp_dir().join("product_calendar_overlay.json");
+        overlay.save(&path).unwrap();
+        assert_eq!(Overlay::load(&path).unwrap(), overlay);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_holiday_names_and_transfers() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        let day = |m, d| {
+            pc.info_by_date(NaiveDate::from_ymd_opt(2024, m, d).unwrap())
+                .unwrap()
+        };
+        assert_eq!(day(5, 9).get_name(), Some("День Победы"));
+        assert_eq!(day(1, 7).get_name(), Some("Рождество Христово"));
+        assert_eq!(
+            day(12, 31).get_transferred_from(),
+            NaiveDate::from_ymd_opt(2024, 1, 7)
+        );
+        assert_eq!(
+            day(4, 27).get_transferred_to(),
+            NaiveDate::from_ymd_opt(2024, 4, 29)
+        );
+        assert_eq!(day(4, 29).get_name(), None);
+    }
+
+    #[test]
+    fn test_info_by_date_sparse() {
+        let pc = get_product_calendar_for_year(Some(2024)).by_kind(DayKind::Preholiday);
+        let date = NaiveDate::from_ymd_opt(2024, 11, 2).unwrap();
+        assert_eq!(pc.info_by_date(date).unwrap().get_date(), date);
+        assert!(pc
+            .info_by_date(NaiveDate::from_ymd_opt(2024, 11, 3).unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn test_work_days_between() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            pc.work_days_between(date(2024, 1, 1), date(2024, 12, 31))
+                .unwrap(),
+            248
+        );
+        assert_eq!(
+            pc.work_hours_between(date(2024, 1, 1), date(2024, 12, 31), WeekLength::Forty)
+                .unwrap(),
+            1979.0
+        );
+        assert_eq!(
+            pc.work_hours_between(date(2024, 1, 1), date(2024, 12, 31), WeekLength::ThirtySix)
+                .unwrap(),
+            1780.6
+        );
+        assert_eq!(
+            pc.statistic_between(date(2024, 5, 1), date(2024, 5, 31))
+                .unwrap(),
+            pc.period_slice(date(2024, 5, 1), date(2024, 5, 31))
+                .unwrap()
+                .statistic()
+        );
+        //Период за пределами календаря - с подгрузкой следующего года
+        assert_eq!(
+            pc.work_days_between(date(2024, 12, 1), date(2025, 1, 31))
+                .unwrap(),
+            21 + 17
+        );
+        assert!(pc
+            .work_days_between(date(2024, 2, 1), date(2024, 1, 1))
+            .is_err());
+
+        //Накопленные количества пересчитываются после изменений календаря
+        let mut overlay = Overlay::new();
+        overlay.add(date(2024, 5, 13), DayKind::Holiday, "");
+        let pc = pc.with_overlay(&overlay);
+        assert_eq!(
+            pc.work_days_between(date(2024, 1, 1), date(2024, 12, 31))
+                .unwrap(),
+            247
+        );
+    }
+
+    #[test]
+    fn test_add_work_days() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        //Пятница перед майскими праздниками
+        assert_eq!(
+            pc.add_work_days(date(2024, 5, 3), 1).unwrap().get_date(),
+            date(2024, 5, 6)
+        );
+        assert_eq!(
+            pc.add_work_days(date(2024, 5, 13), -5).unwrap().get_date(),
+            date(2024, 5, 2)
+        );
+        assert_eq!(
+            pc.add_work_days(date(2024, 5, 11), 0).unwrap().get_date(),
+            date(2024, 5, 11)
+        );
+        //Через границу года в обе стороны
+        assert_eq!(
+            pc.add_work_days(date(2024, 1, 9), -2).unwrap().get_date(),
+            date(2023, 12, 28)
+        );
+        assert_eq!(
+            pc.add_work_days(date(2024, 12, 28), 1).unwrap().get_date(),
+            date(2025, 1, 9)
+        );
+        assert_eq!(
+            pc.previous_work_day(date(2024, 1, 9)).unwrap().get_date(),
+            date(2023, 12, 29)
+        );
+    }
+
+    #[test]
+    fn test_adjust() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let adjust = |d, convention| pc.adjust(d, convention).unwrap().get_date();
+
+        //Рабочий день не переносится
+        assert_eq!(
+            adjust(date(2024, 5, 8), BusinessDayConvention::Preceding),
+            date(2024, 5, 8)
+        );
+        assert_eq!(
+            adjust(date(2024, 5, 9), BusinessDayConvention::Following),
+            date(2024, 5, 13)
+        );
+        assert_eq!(
+            adjust(date(2024, 5, 9), BusinessDayConvention::Preceding),
+            date(2024, 5, 8)
+        );
+        //30 и 31 марта 2024 - выходные в конце месяца
+        assert_eq!(
+            adjust(date(2024, 3, 30), BusinessDayConvention::Following),
+            date(2024, 4, 1)
+        );
+        assert_eq!(
+            adjust(date(2024, 3, 30), BusinessDayConvention::ModifiedFollowing),
+            date(2024, 3, 29)
+        );
+        assert_eq!(
+            adjust(date(2024, 5, 9), BusinessDayConvention::ModifiedFollowing),
+            date(2024, 5, 13)
+        );
+    }
+
+    #[test]
+    fn test_month_helpers() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let may = pc.extract_dates_in_month(5).unwrap();
+        assert_eq!(may.total_days(), 31);
+        assert_eq!(may.statistic().work_days + may.statistic().preholidays, 20);
+        assert!(pc.extract_dates_in_month(13).is_err());
+
+        assert_eq!(
+            pc.first_work_day_of_month(2024, 1).unwrap().get_date(),
+            date(2024, 1, 9)
+        );
+        assert_eq!(
+            pc.nth_work_day_of_month(2024, 5, 3).unwrap().get_date(),
+            date(2024, 5, 6)
+        );
+        assert_eq!(
+            pc.last_work_day_of_month(2024, 3).unwrap().get_date(),
+            date(2024, 3, 29)
+        );
+        //Рабочая суббота 28 декабря
+        assert_eq!(
+            pc.last_work_day_of_month(2024, 12).unwrap().get_date(),
+            date(2024, 12, 28)
+        );
+        //Следующий год подгружается
+        assert_eq!(
+            pc.first_work_day_of_month(2025, 1).unwrap().get_date(),
+            date(2025, 1, 9)
+        );
+        assert!(pc.nth_work_day_of_month(2024, 5, 21).is_err());
+        assert!(pc.nth_work_day_of_month(2024, 5, 0).is_err());
+    }
+
+    #[test]
+    fn test_quarters_by_dates() {
+        let pc = get_product_calendar_for_year(Some(2024));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let third = pc.extract_dates_in_quarter(3).unwrap();
+        assert_eq!(third.first().unwrap().get_date(), date(2024, 7, 1));
+        assert_eq!(third.last().unwrap().get_date(), date(2024, 9, 30));
+        assert!(pc.extract_dates_in_quarter(5).is_err());
+
+        //Период, начинающийся не с 1 января
+        let period = pc
+            .period_slice(date(2024, 2, 15), date(2024, 8, 10))
+            .unwrap();
+        let second = period.extract_dates_in_quarter(2).unwrap();
+        assert_eq!(second.total_days(), 91);
+        assert_eq!(
+            second.statistic().work_days + second.statistic().preholidays,
+            60
+        );
+        let second_half = period.extract_dates_in_half_year(2).unwrap();
+        assert_eq!(second_half.first().unwrap().get_date(), date(2024, 7, 1));
+
+        let range =
+            get_product_calendar_range(date(2023, 11, 1), date(2025, 2, 28), default_source())
+                .unwrap();
+        let quarters = range.by_quarters();
+        let keys: Vec<String> = quarters.iter().map(|(k, _)| k.to_string()).collect();
+        assert_eq!(
+            keys,
+            ["2023-Q4", "2024-Q1", "2024-Q2", "2024-Q3", "2024-Q4", "2025-Q1"]
+        );
+        assert_eq!(quarters[0].1 .0.total_days(), 61);
+        assert_eq!(
+            quarters[1].1 .1,
+            pc.extract_dates_in_quarter(1).unwrap().statistic()
+        );
+        assert_eq!(range.by_half_years().len(), 4);
+    }
+
+    #[test]
+    fn test_by_weeks() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let pc =
+            get_product_calendar_range(date(2024, 12, 25), date(2025, 1, 15), default_source())
+                .unwrap();
+        let weeks = pc.by_weeks();
+
+        let keys: Vec<String> = weeks.iter().map(|(k, _)| k.to_string()).collect();
+        assert_eq!(keys, ["2024-W52", "2025-W01", "2025-W02", "2025-W03"]);
+        //Неполная неделя в начале периода: среда - воскресенье, 28 декабря - рабочая суббота
+        let (first, statistic) = &weeks[0].1;
+        assert_eq!(first.total_days(), 5);
+        assert_eq!(statistic.work_days + statistic.preholidays, 4);
+        //30 декабря 2024 - 5 января 2025 относится к первой неделе 2025 года
+        let (new_year, statistic) = &weeks[1].1;
+        assert_eq!(new_year.first().unwrap().get_date(), date(2024, 12, 30));
+        assert_eq!(statistic.holidays, 5);
+        assert_eq!(weeks[3].1 .0.total_days(), 3);
+    }
+
+    #[test]
+    fn test_group_by() {
+        let pc = get_product_calendar_for_year(Some(2024));
+
+        let months = pc.group_by(Grouping::Month);
+        assert_eq!(months.len(), 12);
+        assert_eq!(months[4].0.to_string(), "2024-05");
+        let (may, statistic) = &months[4].1;
+        assert_eq!(may.total_days(), 31);
+        assert_eq!(
+            *statistic,
+            pc.extract_dates_in_month(5).unwrap().statistic()
+        );
+
+        let quarters = pc.group_by(Grouping::Quarter);
+        let total: u16 = quarters
+            .iter()
+            .map(|(_, (_, s))| s.work_days + s.preholidays)
+            .sum();
+        assert_eq!(total, 248);
+        assert_eq!(pc.group_by(Grouping::HalfYear).len(), 2);
+
+        //Произвольные группы не обязаны идти подряд
+        let by_weekday = pc.group_by_custom(|day| day.get_weekday().to_string());
+        assert_eq!(by_weekday.len(), 7);
+        assert_eq!(by_weekday[0].0, PeriodKey::Custom("Mon".to_string()));
+        assert_eq!(by_weekday[0].1 .0.total_days(), 53);
+    }
+
+    #[test]
+    fn test_fiscal_year() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let april = FiscalYear::new(4).unwrap();
+        let pc = get_product_calendar_fiscal(2024, april, default_source()).unwrap();
+
+        assert_eq!(pc.first().unwrap().get_date(), date(2024, 4, 1));
+        assert_eq!(pc.last().unwrap().get_date(), date(2025, 3, 31));
+
+        //Четвертый квартал финансового года - первый квартал 2025 календарного
+        let fourth = pc.extract_dates_in_quarter(4).unwrap();
+        assert_eq!(fourth.first().unwrap().get_date(), date(2025, 1, 1));
+        let calendar_q1 = get_product_calendar_for_year(Some(2025))
+            .extract_dates_in_quarter(1)
+            .unwrap();
+        assert_eq!(fourth.statistic(), calendar_q1.statistic());
+
+        let quarters = pc.by_quarters();
+        let keys: Vec<String> = quarters.iter().map(|(k, _)| k.to_string()).collect();
+        assert_eq!(keys, ["2024-Q1", "2024-Q2", "2024-Q3", "2024-Q4"]);
+        let second_half = pc.extract_dates_in_half_year(2).unwrap();
+        assert_eq!(second_half.first().unwrap().get_date(), date(2024, 10, 1));
+
+        let years = pc.group_by(Grouping::Year);
+        assert_eq!(years.len(), 1);
+        assert_eq!(years[0].1 .1, pc.statistic());
+
+        //Разбивка сохраняется для подпериодов
+        let period = pc
+            .period_slice(date(2025, 2, 1), date(2025, 4, 30))
+            .unwrap();
+        let keys: Vec<String> = period
+            .group_by(Grouping::Year)
+            .iter()
+            .map(|(k, _)| k.to_string())
+            .collect();
+        assert_eq!(keys, ["2024", "2025"]);
+    }
+
+    #[test]
+    fn test_search_is_bounded() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        //Источник без единого рабочего дня
+        #[derive(Debug, Default)]
+        struct HolidaySource(AtomicUsize);
+
+        impl CalendarSource for HolidaySource {
+            fn name(&self) -> String {
+                "holidays".to_string()
+            }
+
+            fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn std::error::Error>> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                let start = NaiveDate::from_ymd_opt(year as i32, 1, 1).unwrap();
+                Ok(start
+                    .iter_days()
+                    .take_while(|d| d.year() == year as i32)
+                    .map(|d| {
+                        let mut day = Day::new(d);
+                        day.set_kind(DayKind::Holiday);
+                        day
+                    })
+                    .collect())
+            }
+        }
+
+        let source = Arc::new(HolidaySource::default());
+        let pc = get_product_calendar_from(Some(2040), source.clone()).unwrap();
+        let date = NaiveDate::from_ymd_opt(2040, 6, 1).unwrap();
+
+        assert!(pc.next_work_day(date).is_err());
+        assert!(pc.previous_work_day(date).is_err());
+        assert!(source.0.load(Ordering::SeqCst) <= 2 * MAX_LOADED_YEARS as usize + 1);
+    }
 }
@@ -0,0 +1,149 @@
+use crate::errors::ProductCalendarError;
+use chrono::{Datelike, Months, NaiveDate};
+use std::fmt;
+
+/// Период, по которому сгруппированы дни календаря.
+/// Год квартала, полугодия и года - финансовый (см. `FiscalYear`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PeriodKey {
+    Month { year: i32, month: u8 },
+    Quarter { year: i32, quarter: u8 },
+    HalfYear { year: i32, half: u8 },
+    Year { year: i32 },
+    //Неделя по ISO 8601: год недели может отличаться от календарного на стыке лет
+    Week { year: i32, week: u8 },
+    //Произвольная группа, заданная пользователем
+    Custom(String),
+}
+
+/// Стандартный способ группировки дней календаря.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Grouping {
+    Month,
+    Quarter,
+    HalfYear,
+    Year,
+    Week,
+}
+
+impl Grouping {
+    pub fn key(&self, date: NaiveDate, fiscal: FiscalYear) -> PeriodKey {
+        match self {
+            Grouping::Month => PeriodKey::Month {
+                year: date.year(),
+                month: date.month() as u8,
+            },
+            Grouping::Quarter => PeriodKey::Quarter {
+                year: fiscal.year_of(date),
+                quarter: fiscal.quarter_of(date),
+            },
+            Grouping::HalfYear => PeriodKey::HalfYear {
+                year: fiscal.year_of(date),
+                half: fiscal.half_year_of(date),
+            },
+            Grouping::Year => PeriodKey::Year {
+                year: fiscal.year_of(date),
+            },
+            Grouping::Week => {
+                let week = date.iso_week();
+                PeriodKey::Week {
+                    year: week.year(),
+                    week: week.week() as u8,
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for PeriodKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeriodKey::Month { year, month } => write!(f, "{}-{:02}", year, month),
+            PeriodKey::Quarter { year, quarter } => write!(f, "{}-Q{}", year, quarter),
+            PeriodKey::HalfYear { year, half } => write!(f, "{}-H{}", year, half),
+            PeriodKey::Year { year } => write!(f, "{}", year),
+            PeriodKey::Week { year, week } => write!(f, "{}-W{:02}", year, week),
+            PeriodKey::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Финансовый год, начинающийся с первого числа месяца `start_month`.
+/// Год обозначается календарным годом своего начала: финансовый 2024 год
+/// с началом в апреле длится с 1 апреля 2024 по 31 марта 2025.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiscalYear {
+    start_month: u8,
+}
+
+impl Default for FiscalYear {
+    fn default() -> Self {
+        Self { start_month: 1 }
+    }
+}
+
+impl FiscalYear {
+    pub fn new(start_month: u8) -> Result<Self, ProductCalendarError> {
+        if !(1..=12).contains(&start_month) {
+            return Err(ProductCalendarError::InvalidMonth(start_month));
+        }
+        Ok(Self { start_month })
+    }
+
+    pub fn start_month(&self) -> u8 {
+        self.start_month
+    }
+
+    //Номер месяца от начала финансового года, с нуля
+    fn month_offset(&self, date: NaiveDate) -> u32 {
+        (date.month0() + 13 - self.start_month as u32) % 12
+    }
+
+    pub fn year_of(&self, date: NaiveDate) -> i32 {
+        if date.month() >= self.start_month as u32 {
+            date.year()
+        } else {
+            date.year() - 1
+        }
+    }
+
+    pub fn quarter_of(&self, date: NaiveDate) -> u8 {
+        (self.month_offset(date) / 3 + 1) as u8
+    }
+
+    pub fn half_year_of(&self, date: NaiveDate) -> u8 {
+        (self.month_offset(date) / 6 + 1) as u8
+    }
+
+    /// Первый и последний день финансового года `year`.
+    pub fn bounds(&self, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+        let start = NaiveDate::from_ymd_opt(year, self.start_month as u32, 1)?;
+        let end = start.checked_add_months(Months::new(12))?.pred_opt()?;
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fiscal_year() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let april = FiscalYear::new(4).unwrap();
+
+        assert_eq!(april.year_of(date(2025, 3, 31)), 2024);
+        assert_eq!(april.quarter_of(date(2024, 4, 1)), 1);
+        assert_eq!(april.quarter_of(date(2025, 1, 15)), 4);
+        assert_eq!(april.half_year_of(date(2024, 10, 1)), 2);
+        assert_eq!(
+            april.bounds(2024),
+            Some((date(2024, 4, 1), date(2025, 3, 31)))
+        );
+
+        let calendar = FiscalYear::default();
+        assert_eq!(calendar.quarter_of(date(2024, 12, 31)), 4);
+        assert_eq!(calendar.year_of(date(2024, 12, 31)), 2024);
+        assert!(FiscalYear::new(0).is_err());
+    }
+}
@@ -0,0 +1,1844 @@
+// Код, генерируемый pyo3 0.22 для `PyResult`, срабатывает на этот lint.
+// Lint отключен только для привязок к python, а не для всего крейта
+#![allow(clippy::useless_conversion)]
+
+use crate::accounting::PeriodBalance as RustPeriodBalance;
+use crate::convention::BusinessDayConvention as RustBusinessDayConvention;
+use crate::day::{kind::DayKind, Day as RustDay};
+use crate::deadline::{Deadline as RustDeadline, PeriodUnit as RustPeriodUnit};
+use crate::employee::{
+    AbsenceKind as RustAbsenceKind, EmployeeCalendar as RustEmployeeCalendar,
+    EmployeeStatistic as RustEmployeeStatistic,
+};
+use crate::overlay::Overlay as RustOverlay;
+use crate::pc::{
+    get_product_calendar_fiscal, get_product_calendar_from, get_product_calendar_range,
+    ProductCalendar as RustProductCalendar,
+};
+use crate::period::{FiscalYear, Grouping as RustGrouping, PeriodKey};
+use crate::region::Region;
+use crate::schedule::WorkSchedule as RustWorkSchedule;
+use crate::shift::{
+    Shift as RustShift, ShiftPattern, ShiftSchedule as RustShiftSchedule,
+    ShiftSummary as RustShiftSummary,
+};
+use crate::source::{default_source, source_from_spec, CalendarSource};
+use crate::statistic::{Statistic as RustStatistic, WeekLength};
+use crate::{accounting, cache, deadline, errors, source};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{IntoPyDict, PyDict, PyString};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Расхождение календаря с официальными нормами, в отличие от ошибок загрузки норм
+pyo3::create_exception!(product_calendar, NormMismatchError, PyValueError);
+
+/// Источник данных, реализованный на python: вызываемый объект,
+/// принимающий год и возвращающий список словарей `{"day": date, "kind": str}`
+/// с необязательными ключами `name`, `transferred_from` и `transferred_to`.
+/// Не кэшируется: у вызываемого объекта нет имени, устойчивого между вызовами,
+/// а кэш удерживал бы его от удаления.
+#[derive(Debug)]
+struct PySource(PyObject);
+
+impl CalendarSource for PySource {
+    fn name(&self) -> String {
+        "python".to_string()
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    fn persistent_cache(&self) -> bool {
+        false
+    }
+
+    fn fetch(&self, year: u16) -> Result<Vec<RustDay>, Box<dyn Error>> {
+        Python::with_gil(|py| {
+            let items: Vec<Bound<'_, PyDict>> = self.0.call1(py, (year,))?.extract(py)?;
+            let mut days = Vec::with_capacity(items.len());
+            for item in items {
+                let date: NaiveDate = item
+                    .get_item("day")?
+                    .ok_or("Отсутствует ключ `day`")?
+                    .extract()?;
+                let kind: String = item
+                    .get_item("kind")?
+                    .ok_or("Отсутствует ключ `kind`")?
+                    .extract()?;
+                let mut day = RustDay::new(date);
+                day.set_kind(DayKind::from_str(&kind)?);
+                if let Some(name) = item.get_item("name")? {
+                    day.set_name(name.extract()?);
+                }
+                if let Some(from) = item.get_item("transferred_from")? {
+                    day.set_transferred_from(from.extract()?);
+                }
+                if let Some(to) = item.get_item("transferred_to")? {
+                    day.set_transferred_to(to.extract()?);
+                }
+                days.push(day);
+            }
+            Ok(days)
+        })
+    }
+}
+
+fn extract_source(source: Option<&Bound<'_, PyAny>>) -> PyResult<Arc<dyn CalendarSource>> {
+    match source {
+        None => Ok(default_source()),
+        Some(s) if s.is_instance_of::<PyString>() => Ok(source_from_spec(&s.extract::<String>()?)),
+        Some(s) if s.is_callable() => Ok(Arc::new(PySource(s.clone().unbind()))),
+        Some(_) => Err(PyErr::new::<PyTypeError, _>(
+            "source должен быть строкой или вызываемым объектом",
+        )),
+    }
+}
+
+#[pyclass]
+pub struct ProductCalendar(RustProductCalendar);
+
+/// Правило переноса даты, выпавшей на выходной или праздник.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum BusinessDayConvention {
+    Following,
+    ModifiedFollowing,
+    Preceding,
+}
+
+/// Единица исчисления срока.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum PeriodUnit {
+    CalendarDays,
+    WorkDays,
+    Months,
+    Years,
+}
+
+/// Стандартный способ группировки дней календаря.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Grouping {
+    Month,
+    Quarter,
+    HalfYear,
+    Year,
+    Week,
+}
+
+/// Причина отсутствия сотрудника на работе.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum AbsenceKind {
+    Vacation,
+    SickLeave,
+    BusinessTrip,
+    UnpaidLeave,
+}
+
+impl From<AbsenceKind> for RustAbsenceKind {
+    fn from(kind: AbsenceKind) -> Self {
+        match kind {
+            AbsenceKind::Vacation => RustAbsenceKind::Vacation,
+            AbsenceKind::SickLeave => RustAbsenceKind::SickLeave,
+            AbsenceKind::BusinessTrip => RustAbsenceKind::BusinessTrip,
+            AbsenceKind::UnpaidLeave => RustAbsenceKind::UnpaidLeave,
+        }
+    }
+}
+
+impl From<RustAbsenceKind> for AbsenceKind {
+    fn from(kind: RustAbsenceKind) -> Self {
+        match kind {
+            RustAbsenceKind::Vacation => AbsenceKind::Vacation,
+            RustAbsenceKind::SickLeave => AbsenceKind::SickLeave,
+            RustAbsenceKind::BusinessTrip => AbsenceKind::BusinessTrip,
+            RustAbsenceKind::UnpaidLeave => AbsenceKind::UnpaidLeave,
+        }
+    }
+}
+
+impl From<Grouping> for RustGrouping {
+    fn from(grouping: Grouping) -> Self {
+        match grouping {
+            Grouping::Month => RustGrouping::Month,
+            Grouping::Quarter => RustGrouping::Quarter,
+            Grouping::HalfYear => RustGrouping::HalfYear,
+            Grouping::Year => RustGrouping::Year,
+            Grouping::Week => RustGrouping::Week,
+        }
+    }
+}
+
+impl From<PeriodUnit> for RustPeriodUnit {
+    fn from(unit: PeriodUnit) -> Self {
+        match unit {
+            PeriodUnit::CalendarDays => RustPeriodUnit::CalendarDays,
+            PeriodUnit::WorkDays => RustPeriodUnit::WorkDays,
+            PeriodUnit::Months => RustPeriodUnit::Months,
+            PeriodUnit::Years => RustPeriodUnit::Years,
+        }
+    }
+}
+
+impl From<BusinessDayConvention> for RustBusinessDayConvention {
+    fn from(convention: BusinessDayConvention) -> Self {
+        match convention {
+            BusinessDayConvention::Following => RustBusinessDayConvention::Following,
+            BusinessDayConvention::ModifiedFollowing => {
+                RustBusinessDayConvention::ModifiedFollowing
+            }
+            BusinessDayConvention::Preceding => RustBusinessDayConvention::Preceding,
+        }
+    }
+}
+
+//Словарь python сохраняет порядок добавления ключей
+fn grouped_to_dict(
+    py: Python<'_>,
+    groups: Vec<(PeriodKey, (RustProductCalendar, RustStatistic))>,
+) -> PyResult<Bound<'_, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    for (key, (pc, statistic)) in groups {
+        dict.set_item(
+            key.to_string(),
+            (
+                ProductCalendar(pc).into_py(py),
+                Statistic(statistic).into_py(py),
+            ),
+        )?;
+    }
+    Ok(dict)
+}
+
+//Момент времени из python: наивный `datetime` - местное время, с часовым поясом - абсолютное
+enum Moment {
+    Local(NaiveDateTime),
+    Absolute(DateTime<Utc>),
+}
+
+fn extract_moment(moment: &Bound<'_, PyAny>) -> PyResult<Moment> {
+    if moment.getattr("tzinfo")?.is_none() {
+        return Ok(Moment::Local(moment.extract()?));
+    }
+    let utc = PyModule::import_bound(moment.py(), "datetime")?
+        .getattr("timezone")?
+        .getattr("utc")?;
+    Ok(Moment::Absolute(
+        moment.call_method1("astimezone", (utc,))?.extract()?,
+    ))
+}
+
+fn parse_time_zone(time_zone: Option<&str>) -> PyResult<Option<Tz>> {
+    time_zone
+        .map(|name| {
+            Tz::from_str(name).map_err(|_| {
+                PyErr::new::<PyValueError, _>(format!("Неизвестный часовой пояс: `{}`", name))
+            })
+        })
+        .transpose()
+}
+
+#[pymethods]
+impl ProductCalendar {
+    #[new]
+    /// Создает новый экземпляр ProductCalendar.
+    ///
+    /// # Аргументы
+    /// * `year` - Опциональный год для календаря.
+    /// * `source` - Опциональный источник данных: адрес зеркала consultant.ru,
+    ///   путь к каталогу с файлами `<год>.json` или функция `year -> list[dict]`.
+    /// * `region` - Опциональный регион с собственными праздниками (например, "Tatarstan").
+    /// * `fiscal_start` - Опциональный месяц начала финансового года: календарь строится
+    ///   за финансовый год `year` и захватывает два календарных года.
+    ///
+    /// # Пример
+    /// ```
+    /// from product_calendar import ProductCalendar
+    ///
+    ///
+    /// calendar = ProductCalendar(2024)
+    /// local = ProductCalendar(2024, source="/opt/calendars")
+    /// kazan = ProductCalendar(2024, region="Tatarstan")
+    /// fiscal = ProductCalendar(2024, fiscal_start=4)  # 01.04.2024 - 31.03.2025
+    /// ```
+    #[pyo3(signature=(year=None, source=None, region=None, fiscal_start=None))]
+    fn new(
+        year: Option<u16>,
+        source: Option<&Bound<'_, PyAny>>,
+        region: Option<&str>,
+        fiscal_start: Option<u8>,
+    ) -> PyResult<Self> {
+        let region = region
+            .map(Region::from_str)
+            .transpose()
+            .map_err(PyErr::new::<PyValueError, _>)?;
+        let source = extract_source(source)?;
+        let calendar = match fiscal_start {
+            Some(month) => {
+                let fiscal_year = FiscalYear::new(month)
+                    .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+                let year = year.unwrap_or_else(|| {
+                    fiscal_year.year_of(chrono::Local::now().date_naive()) as u16
+                });
+                get_product_calendar_fiscal(year, fiscal_year, source)
+            }
+            None => get_product_calendar_from(year, source),
+        };
+        match calendar {
+            Ok(rpc) => match region {
+                Some(region) => Ok(Self(rpc.with_region(region))),
+                None => Ok(Self(rpc)),
+            },
+            Err(e) => Err(PyErr::new::<PyRuntimeError, _>(e.to_string())),
+        }
+    }
+
+    /// Создает календарь за произвольный период, в том числе охватывающий несколько лет.
+    ///
+    /// # Аргументы
+    /// * `start` - Начальная дата.
+    /// * `end` - Конечная дата.
+    /// * `source` - Опциональный источник данных (см. конструктор).
+    ///
+    /// # Пример
+    /// ```
+    /// from product_calendar import ProductCalendar
+    ///
+    ///
+    /// calendar = ProductCalendar.range(date(2024, 12, 1), date(2025, 1, 31))
+    /// ```
+    #[staticmethod]
+    #[pyo3(signature=(start, end, source=None))]
+    fn range(
+        start: NaiveDate,
+        end: NaiveDate,
+        source: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        match get_product_calendar_range(start, end, extract_source(source)?) {
+            Ok(rpc) => Ok(Self(rpc)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает день после указанного количества недель от заданной даты.
+    ///
+    /// # Аргументы
+    /// * `date` - Начальная дата.
+    /// * `weeks` - Количество недель.
+    /// # Пример
+    /// ```
+    /// let calendar = ProductCalendar::new(Some(2024));
+    /// let desired_day = calendar.after_nth_weeks()
+    /// ```
+    fn after_nth_weeks(&self, date: NaiveDate, weeks: usize) -> PyResult<Day> {
+        match self.0.after_nth_weeks(date, weeks) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Создает новый экземпляр Day.
+    /// # Аргументы
+    /// * `date` - дата по которой требуется информация.
+    /// # Пример
+    /// ```
+    /// let calendar = ProductCalendar::new(Some(2024));
+    /// let day = calendar.info_by_date(NaiveDate::from_ymd_opt(2024, 6, 11).unwrap())
+    fn info_by_date(&self, date: NaiveDate) -> PyResult<Option<Day>> {
+        match self.0.info_by_date(date) {
+            Some(d) => Ok(Some(Day(d))),
+            None => Ok(None),
+        }
+    }
+
+    /// Возвращает последний день текущего периода
+    ///
+    /// # Пример
+    /// ```
+    /// from product_calendar import ProductCalendar
+    ///
+    ///
+    /// calendar = ProductCalendar(2024)
+    /// print(calendar.last())
+    /// ```
+    fn last(&self) -> PyResult<Option<Day>> {
+        match self.0.last() {
+            Some(d) => Ok(Some(Day(d.clone()))),
+            None => Ok(None),
+        }
+    }
+    ///
+    /// Возвращает первый день текущего периода
+    ///
+    /// # Пример
+    /// ```
+    /// from product_calendar import ProductCalendar
+    ///
+    ///
+    /// calendar = ProductCalendar(2024)
+    /// print(calendar.first())
+    /// ```
+    fn first(&self) -> PyResult<Option<Day>> {
+        match self.0.first() {
+            Some(d) => Ok(Some(Day(d.clone()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Возвращает календарь за указанный период начиная с даты и длиной в количество дней.
+    ///
+    /// # Аргументы
+    /// * `date` - Начальная дата.
+    /// * `days` - Количество дней.
+    fn period_by_number_of_days(&self, date: NaiveDate, days: usize) -> PyResult<Self> {
+        match self.0.period_by_number_of_days(date, days) {
+            Ok(rpc) => Ok(Self(rpc)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Расшираяет календарь на переданное кол-во дней с конца
+    ///
+    /// # Аргументы
+    /// * `days` - Количество дней.
+    fn extend_forward(&mut self, days: usize) -> PyResult<()> {
+        match self.0.extend_forward(days) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Расшираяет календарь на переданное кол-во дней с начала
+    ///
+    /// # Аргументы
+    /// * `days` - Количество дней.
+    fn extend_backward(&mut self, days: usize) -> PyResult<()> {
+        match self.0.extend_backward(days) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает календарь за указанный период начиная с даты и длиной в количество рабочих дней.
+    ///
+    /// # Аргументы
+    /// * `date` - Начальная дата.
+    /// * `work_days` - Количество рабочих дней.
+    fn period_by_number_of_work_days(&self, date: NaiveDate, work_days: usize) -> PyResult<Self> {
+        match self.0.period_by_number_of_work_days(date, work_days) {
+            Ok(rpc) => Ok(Self(rpc)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает календарь за указанный период между двумя датами.
+    ///
+    /// # Аргументы
+    /// * `start` - Начальная дата.
+    /// * `end` - Конечная дата.
+    fn period_slice(&self, start: NaiveDate, end: NaiveDate) -> PyResult<Self> {
+        match self.0.period_slice(start, end) {
+            Ok(rpc) => Ok(Self(rpc)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает количество рабочих дней (включая предпраздничные) между двумя датами включительно.
+    ///
+    /// # Аргументы
+    /// * `start` - Начальная дата.
+    /// * `end` - Конечная дата.
+    fn work_days_between(&self, start: NaiveDate, end: NaiveDate) -> PyResult<u32> {
+        self.0
+            .work_days_between(start, end)
+            .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+    }
+
+    /// Возвращает норму рабочих часов между двумя датами включительно.
+    ///
+    /// # Аргументы
+    /// * `start` - Начальная дата.
+    /// * `end` - Конечная дата.
+    /// * `week` - Продолжительность рабочей недели в часах: 40, 36 или 24.
+    #[pyo3(signature=(start, end, week=40))]
+    fn work_hours_between(&self, start: NaiveDate, end: NaiveDate, week: u16) -> PyResult<f64> {
+        let map_err =
+            |e: errors::ProductCalendarError| PyErr::new::<PyValueError, _>(e.to_string());
+        let week = WeekLength::from_hours(week).map_err(map_err)?;
+        self.0.work_hours_between(start, end, week).map_err(map_err)
+    }
+
+    /// Возвращает статистику по дням между двумя датами включительно.
+    ///
+    /// # Аргументы
+    /// * `start` - Начальная дата.
+    /// * `end` - Конечная дата.
+    fn statistic_between(&self, start: NaiveDate, end: NaiveDate) -> PyResult<Statistic> {
+        self.0
+            .statistic_between(start, end)
+            .map(Statistic)
+            .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+    }
+
+    /// Возвращает даты за указанный квартал.
+    ///
+    /// # Аргументы
+    /// * `quarter` - Номер квартала (1, 2, 3 или 4).
+    fn extract_dates_in_quarter(&self, quarter: u8) -> PyResult<Self> {
+        match self.0.extract_dates_in_quarter(quarter) {
+            Ok(calendar) => Ok(Self(calendar)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает календарь, в котором кварталы, полугодия и годы отсчитываются
+    /// от начала финансового года.
+    ///
+    /// # Аргументы
+    /// * `start_month` - Месяц начала финансового года (от 1 до 12).
+    fn with_fiscal_year(&self, start_month: u8) -> PyResult<Self> {
+        match FiscalYear::new(start_month) {
+            Ok(fiscal_year) => Ok(Self(self.0.with_fiscal_year(fiscal_year))),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Месяц начала финансового года календаря.
+    #[getter]
+    fn fiscal_start(&self) -> PyResult<u8> {
+        Ok(self.0.fiscal_year().start_month())
+    }
+
+    /// Возвращает даты за указанное полугодие.
+    ///
+    /// # Аргументы
+    /// * `half` - Номер полугодия (1 или 2).
+    fn extract_dates_in_half_year(&self, half: u8) -> PyResult<Self> {
+        match self.0.extract_dates_in_half_year(half) {
+            Ok(calendar) => Ok(Self(calendar)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает календарь, разбитый на кварталы: словарь
+    /// `{"2024-Q1": (ProductCalendar, Statistic), ...}` в порядке следования кварталов.
+    fn by_quarters<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        grouped_to_dict(py, self.0.by_quarters())
+    }
+
+    /// Возвращает календарь, разбитый на полугодия: словарь
+    /// `{"2024-H1": (ProductCalendar, Statistic), ...}`.
+    fn by_half_years<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        grouped_to_dict(py, self.0.by_half_years())
+    }
+
+    /// Возвращает календарь, разбитый на недели по ISO 8601: словарь
+    /// `{"2024-W01": (ProductCalendar, Statistic), ...}`. Крайние недели периода могут быть неполными.
+    fn by_weeks<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        grouped_to_dict(py, self.0.by_weeks())
+    }
+
+    /// Группирует дни календаря и считает статистику каждой группы.
+    ///
+    /// # Аргументы
+    /// * `by` - Способ группировки (`Grouping.Month`, `Grouping.Quarter`, `Grouping.HalfYear`,
+    ///   `Grouping.Week`) или функция `Day -> str`, возвращающая название группы дня.
+    ///
+    /// # Пример
+    /// ```python
+    /// for month, (period, statistic) in calendar.group_by(Grouping.Month).items():
+    ///     print(month, statistic.work_hours())
+    ///
+    /// calendar.group_by(lambda day: day.weekday)
+    /// ```
+    fn group_by<'py>(
+        &self,
+        py: Python<'py>,
+        by: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        if let Ok(grouping) = by.extract::<Grouping>() {
+            return grouped_to_dict(py, self.0.group_by(grouping.into()));
+        }
+        if !by.is_callable() {
+            return Err(PyErr::new::<PyTypeError, _>(
+                "by должен быть значением Grouping или вызываемым объектом",
+            ));
+        }
+
+        //Названия групп вычисляются заранее, чтобы ошибки python-функции не терялись
+        let mut keys = HashMap::with_capacity(self.0.total_days());
+        for day in self.0.iter() {
+            let key = by.call1((Day(day.clone()),))?.str()?.to_string();
+            keys.insert(day.get_date(), key);
+        }
+        grouped_to_dict(
+            py,
+            self.0.group_by_custom(|day| keys[&day.get_date()].clone()),
+        )
+    }
+
+    /// Возвращает даты за указанный месяц.
+    ///
+    /// # Аргументы
+    /// * `month` - Номер месяца (от 1 до 12).
+    fn extract_dates_in_month(&self, month: u8) -> PyResult<Self> {
+        match self.0.extract_dates_in_month(month) {
+            Ok(calendar) => Ok(Self(calendar)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает первый рабочий день месяца.
+    ///
+    /// # Аргументы
+    /// * `year` - Год.
+    /// * `month` - Номер месяца (от 1 до 12).
+    fn first_work_day_of_month(&self, year: i32, month: u8) -> PyResult<Day> {
+        match self.0.first_work_day_of_month(year, month) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает последний рабочий день месяца.
+    ///
+    /// # Аргументы
+    /// * `year` - Год.
+    /// * `month` - Номер месяца (от 1 до 12).
+    fn last_work_day_of_month(&self, year: i32, month: u8) -> PyResult<Day> {
+        match self.0.last_work_day_of_month(year, month) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает n-й рабочий день месяца.
+    ///
+    /// # Аргументы
+    /// * `year` - Год.
+    /// * `month` - Номер месяца (от 1 до 12).
+    /// * `n` - Порядковый номер рабочего дня, начиная с 1.
+    ///
+    /// # Пример
+    /// ```python
+    /// calendar.nth_work_day_of_month(2024, 5, 3)  # 2024-05-06
+    /// ```
+    fn nth_work_day_of_month(&self, year: i32, month: u8, n: usize) -> PyResult<Day> {
+        match self.0.nth_work_day_of_month(year, month, n) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает статистику по календарю.
+    fn statistic(&self) -> PyResult<Statistic> {
+        Ok(Statistic(self.0.statistic()))
+    }
+
+    /// Возвращает общее количество дней в календаре.
+    fn total_days(&self) -> PyResult<usize> {
+        Ok(self.0.total_days())
+    }
+
+    /// Возвращает следующий рабочий день после указанной даты.
+    ///
+    /// # Аргументы
+    /// * `cur_day` - Текущая дата.
+    fn next_work_day(&self, cur_day: NaiveDate) -> PyResult<Day> {
+        match self.0.next_work_day(cur_day) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает предыдущий рабочий день перед указанной датой.
+    ///
+    /// # Аргументы
+    /// * `cur_day` - Текущая дата.
+    fn previous_work_day(&self, cur_day: NaiveDate) -> PyResult<Day> {
+        match self.0.previous_work_day(cur_day) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Переносит дату, выпавшую на выходной или праздник, на рабочий день.
+    ///
+    /// # Аргументы
+    /// * `date` - Исходная дата.
+    /// * `convention` - Правило переноса, по умолчанию `BusinessDayConvention.Following`.
+    ///
+    /// # Пример
+    /// ```python
+    /// calendar.adjust(date(2024, 3, 30), BusinessDayConvention.ModifiedFollowing)  # 2024-03-29
+    /// ```
+    #[pyo3(signature=(date, convention=BusinessDayConvention::Following))]
+    fn adjust(&self, date: NaiveDate, convention: BusinessDayConvention) -> PyResult<Day> {
+        match self.0.adjust(date, convention.into()) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Рассчитывает окончание срока по правилам ст. 191-193 ГК РФ и ст. 6.1 НК РФ.
+    ///
+    /// # Аргументы
+    /// * `start` - Дата события, со следующего дня после которого начинается срок.
+    /// * `length` - Длина срока.
+    /// * `unit` - Единица исчисления срока, по умолчанию `PeriodUnit.CalendarDays`.
+    ///
+    /// # Пример
+    /// ```python
+    /// result = calendar.deadline(date(2024, 4, 29), 10)
+    /// print(result.end, result.explanation)  # 2024-05-13, перенос с 9 мая
+    /// ```
+    #[pyo3(signature=(start, length, unit=PeriodUnit::CalendarDays))]
+    fn deadline(&self, start: NaiveDate, length: u32, unit: PeriodUnit) -> PyResult<Deadline> {
+        match deadline::deadline(&self.0, start, length, unit.into()) {
+            Ok(d) => Ok(Deadline(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Суммированный учет рабочего времени (ст. 104 ТК РФ): сравнивает отработанные часы
+    /// с нормой по учетным периодам. Возвращает словарь "период -> итог периода".
+    ///
+    /// # Аргументы
+    /// * `worked` - Отработанные часы по датам: словарь `{date: часы}` или список `[(date, часы)]`.
+    /// * `period` - Учетный период, по умолчанию `Grouping.Month`.
+    /// * `week` - Продолжительность рабочей недели для нормы: 40, 36 или 24 часа.
+    ///
+    /// # Пример
+    /// ```python
+    /// balance = calendar.summarized_accounting(worked, Grouping.Quarter)
+    /// print(balance["2024-Q1"].overtime())
+    /// ```
+    #[pyo3(signature=(worked, period=Grouping::Month, week=40))]
+    fn summarized_accounting<'py>(
+        &self,
+        py: Python<'py>,
+        worked: &Bound<'py, PyAny>,
+        period: Grouping,
+        week: u16,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let worked: Vec<(NaiveDate, f64)> = match worked.downcast::<PyDict>() {
+            Ok(dict) => dict
+                .extract::<HashMap<NaiveDate, f64>>()?
+                .into_iter()
+                .collect(),
+            Err(_) => worked.extract()?,
+        };
+        let map_err =
+            |e: errors::ProductCalendarError| PyErr::new::<PyValueError, _>(e.to_string());
+        let week = WeekLength::from_hours(week).map_err(map_err)?;
+        let balances =
+            accounting::summarize(&self.0, worked, period.into(), week).map_err(map_err)?;
+
+        let dict = PyDict::new_bound(py);
+        for balance in balances {
+            dict.set_item(
+                balance.period.to_string(),
+                PeriodBalance(balance).into_py(py),
+            )?;
+        }
+        Ok(dict)
+    }
+
+    /// Возвращает день, отстоящий от даты на указанное количество рабочих дней.
+    ///
+    /// # Аргументы
+    /// * `date` - Исходная дата (сама не учитывается).
+    /// * `work_days` - Количество рабочих дней: положительное - вперед, отрицательное - назад.
+    ///
+    /// # Пример
+    /// ```python
+    /// calendar.add_work_days(date(2024, 5, 13), -5)  # 2024-05-02
+    /// ```
+    fn add_work_days(&self, date: NaiveDate, work_days: i64) -> PyResult<Day> {
+        match self.0.add_work_days(date, work_days) {
+            Ok(d) => Ok(Day(d)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает рабочее время между двумя моментами с учетом распорядка дня.
+    /// Наивные `datetime` считаются местным временем календаря, `datetime` с часовым поясом
+    /// переводятся в местное время `time_zone` или часового пояса календаря.
+    ///
+    /// # Аргументы
+    /// * `start` - Начальный момент.
+    /// * `end` - Конечный момент.
+    /// * `schedule` - Распорядок дня, по умолчанию 09:00 - 18:00 с обедом 13:00 - 14:00.
+    /// * `time_zone` - Часовой пояс запроса, например "Asia/Vladivostok".
+    ///
+    /// # Пример
+    /// ```python
+    /// calendar.business_time_between(datetime(2024, 5, 8, 16), datetime(2024, 5, 13, 10))  # 2:00:00
+    /// ```
+    #[pyo3(signature=(start, end, schedule=None, time_zone=None))]
+    fn business_time_between(
+        &self,
+        start: &Bound<'_, PyAny>,
+        end: &Bound<'_, PyAny>,
+        schedule: Option<&WorkSchedule>,
+        time_zone: Option<&str>,
+    ) -> PyResult<ChronoDuration> {
+        let schedule = schedule.map(|s| s.0.clone()).unwrap_or_default();
+        let time_zone = parse_time_zone(time_zone)?;
+        let elapsed = match (extract_moment(start)?, extract_moment(end)?) {
+            (Moment::Local(start), Moment::Local(end)) => schedule.elapsed(&self.0, start, end),
+            (Moment::Absolute(start), Moment::Absolute(end)) => {
+                schedule.elapsed_utc(&self.0, start, end, time_zone)
+            }
+            _ => {
+                return Err(PyErr::new::<PyTypeError, _>(
+                    "start и end должны быть оба с часовым поясом или оба без него",
+                ))
+            }
+        };
+        elapsed.map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+    }
+
+    /// Возвращает момент, наступающий через указанное рабочее время.
+    /// Для `datetime` с часовым поясом результат возвращается в UTC.
+    ///
+    /// # Аргументы
+    /// * `moment` - Начальный момент.
+    /// * `duration` - Рабочее время (`timedelta`), отрицательное - отсчет назад.
+    /// * `schedule` - Распорядок дня, по умолчанию 09:00 - 18:00 с обедом 13:00 - 14:00.
+    /// * `time_zone` - Часовой пояс запроса, например "Asia/Vladivostok".
+    #[pyo3(signature=(moment, duration, schedule=None, time_zone=None))]
+    fn add_business_time(
+        &self,
+        py: Python<'_>,
+        moment: &Bound<'_, PyAny>,
+        duration: ChronoDuration,
+        schedule: Option<&WorkSchedule>,
+        time_zone: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let schedule = schedule.map(|s| s.0.clone()).unwrap_or_default();
+        let time_zone = parse_time_zone(time_zone)?;
+        let map_err =
+            |e: errors::ProductCalendarError| PyErr::new::<PyValueError, _>(e.to_string());
+        match extract_moment(moment)? {
+            Moment::Local(moment) => Ok(schedule
+                .add(&self.0, moment, duration)
+                .map_err(map_err)?
+                .into_py(py)),
+            Moment::Absolute(moment) => Ok(schedule
+                .add_utc(&self.0, moment, duration, time_zone)
+                .map_err(map_err)?
+                .into_py(py)),
+        }
+    }
+
+    /// Возвращает календарь, рабочее время которого отсчитывается по местному времени.
+    ///
+    /// # Аргументы
+    /// * `time_zone` - Часовой пояс, например "Asia/Vladivostok".
+    fn with_time_zone(&self, time_zone: &str) -> PyResult<Self> {
+        let time_zone = parse_time_zone(Some(time_zone))?.unwrap_or(self.0.time_zone());
+        Ok(Self(self.0.with_time_zone(time_zone)))
+    }
+
+    /// Часовой пояс календаря, по умолчанию "Europe/Moscow".
+    #[getter]
+    fn time_zone(&self) -> PyResult<String> {
+        Ok(self.0.time_zone().name().to_string())
+    }
+
+    /// Возвращает календарь, отфильтрованный по типу дня.
+    ///
+    /// # Аргументы
+    /// * `kind` - Тип дня (например, "Work", "Weekend").
+    fn by_kind(&self, kind: &str) -> PyResult<Self> {
+        let kind = DayKind::from_str(kind).unwrap();
+        Ok(Self(self.0.by_kind(kind)))
+    }
+
+    /// Возвращает календарь с корпоративными изменениями поверх текущего.
+    ///
+    /// # Аргументы
+    /// * `overlay` - Корпоративные изменения (Overlay).
+    ///
+    /// # Пример
+    /// ```
+    /// from product_calendar import ProductCalendar, Overlay
+    ///
+    ///
+    /// overlay = Overlay()
+    /// overlay.add(date(2024, 5, 13), "Holiday", "День компании")
+    /// calendar = ProductCalendar(2024).with_overlay(overlay)
+    /// ```
+    fn with_overlay(&self, overlay: &Overlay) -> PyResult<Self> {
+        Ok(Self(self.0.with_overlay(&overlay.0)))
+    }
+
+    /// Возвращает официальные нормы рабочего времени за год по месяцам,
+    /// кварталам и полугодиям или None, если источник их не публикует.
+    ///
+    /// # Аргументы
+    /// * `year` - Год.
+    fn official_norms<'py>(
+        &self,
+        py: Python<'py>,
+        year: u16,
+    ) -> PyResult<Option<Vec<Bound<'py, PyDict>>>> {
+        let norms = self
+            .0
+            .official_norms(year)
+            .map_err(|e| PyErr::new::<PyRuntimeError, _>(e.to_string()))?;
+        let Some(norms) = norms else {
+            return Ok(None);
+        };
+        norms
+            .periods
+            .iter()
+            .map(|(period, norm)| {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("period", period.to_string())?;
+                dict.set_item("calendar_days", norm.calendar_days)?;
+                dict.set_item("work_days", norm.work_days)?;
+                dict.set_item("rest_days", norm.rest_days)?;
+                dict.set_item("hours_40", norm.hours_40)?;
+                dict.set_item("hours_36", norm.hours_36)?;
+                dict.set_item("hours_24", norm.hours_24)?;
+                Ok(dict)
+            })
+            .collect::<PyResult<_>>()
+            .map(Some)
+    }
+
+    /// Сверяет дни календаря с официальными нормами рабочего времени.
+    /// При расхождении вызывает NormMismatchError (наследник ValueError) с описанием
+    /// всех различий, если нормы не удалось получить - RuntimeError.
+    fn validate_norms(&self) -> PyResult<()> {
+        self.0.validate_norms().map_err(|e| {
+            match e.downcast_ref::<errors::ProductCalendarError>() {
+                Some(errors::ProductCalendarError::NormMismatch(_)) => {
+                    NormMismatchError::new_err(e.to_string())
+                }
+                _ => PyErr::new::<PyRuntimeError, _>(e.to_string()),
+            }
+        })
+    }
+
+    /// Возвращает True, если календарь содержит предварительные дни,
+    /// рассчитанные по правилам ТК РФ до публикации официальных данных.
+    fn is_predicted(&self) -> PyResult<bool> {
+        Ok(self.0.is_predicted())
+    }
+
+    /// Возвращает все дни в календаре.
+    fn all_days(&self) -> PyResult<Vec<Day>> {
+        Ok(self.0.iter().map(|d| Day(d.clone())).collect())
+    }
+}
+
+#[pyclass]
+pub struct Statistic(RustStatistic);
+
+#[pymethods]
+impl Statistic {
+    /// Создает новый экземпляр Statistic.
+    ///
+    /// # Аргументы
+    /// * `holidays` - Количество праздничных дней.
+    /// * `work_days` - Количество рабочих дней.
+    /// * `weekends` - Количество выходных дней.
+    /// * `preholidays` - Количество предпраздничных дней.
+    #[new]
+    #[pyo3(signature=(holidays=0, work_days=0, weekends=0, preholidays=0))]
+    fn new(holidays: u16, work_days: u16, weekends: u16, preholidays: u16) -> Self {
+        Self(RustStatistic {
+            holidays,
+            weekends,
+            work_days,
+            preholidays,
+        })
+    }
+
+    /// Возвращает количество рабочих часов.
+    fn work_hours(&self) -> PyResult<u16> {
+        Ok(self.0.work_hours())
+    }
+
+    /// Возвращает норму рабочего времени в часах для указанной продолжительности недели.
+    ///
+    /// # Аргументы
+    /// * `week` - Продолжительность рабочей недели в часах: 40, 36 или 24.
+    #[pyo3(signature=(week=40))]
+    fn work_hours_norm(&self, week: u16) -> PyResult<f64> {
+        match WeekLength::from_hours(week) {
+            Ok(week) => Ok(self.0.work_hours_norm(week)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает количество дней отдыха.
+    fn rest_days(&self) -> PyResult<u16> {
+        Ok(self.0.rest_days())
+    }
+
+    /// Возвращает статистику в виде словаря.
+    fn as_dict<'py>(&self, py: Python<'py>) -> Bound<'py, PyDict> {
+        self.0.as_map().into_py_dict_bound(py)
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self.0))
+    }
+
+    #[getter]
+    fn weekends(&self) -> PyResult<u16> {
+        Ok(self.0.weekends)
+    }
+
+    #[setter]
+    fn set_weekends(&mut self, val: u16) -> PyResult<()> {
+        self.0.weekends = val;
+        Ok(())
+    }
+
+    #[getter]
+    fn holidays(&self) -> PyResult<u16> {
+        Ok(self.0.holidays)
+    }
+
+    #[setter]
+    fn set_holidays(&mut self, val: u16) -> PyResult<()> {
+        self.0.holidays = val;
+        Ok(())
+    }
+
+    #[getter]
+    fn work_days(&self) -> PyResult<u16> {
+        Ok(self.0.work_days)
+    }
+
+    #[setter]
+    fn set_work_days(&mut self, val: u16) -> PyResult<()> {
+        self.0.work_days = val;
+        Ok(())
+    }
+
+    #[getter]
+    fn preholidays(&self) -> PyResult<u16> {
+        Ok(self.0.preholidays)
+    }
+
+    #[setter]
+    fn set_preholidays(&mut self, val: u16) -> PyResult<()> {
+        self.0.preholidays = val;
+        Ok(())
+    }
+}
+
+/// Распорядок рабочего дня.
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct WorkSchedule(RustWorkSchedule);
+
+#[pymethods]
+impl WorkSchedule {
+    /// Создает распорядок рабочего дня.
+    ///
+    /// # Аргументы
+    /// * `start` - Начало дня, по умолчанию 09:00.
+    /// * `end` - Конец дня, по умолчанию 18:00.
+    /// * `breaks` - Перерывы `[(начало, конец)]`. По умолчанию обед 13:00 - 14:00,
+    ///   если не заданы `start` и `end`, иначе без перерывов.
+    /// * `preholiday_reduction` - Сокращение предпраздничного дня, по умолчанию час.
+    ///
+    /// # Пример
+    /// ```python
+    /// from product_calendar import WorkSchedule
+    ///
+    ///
+    /// schedule = WorkSchedule(time(8), time(17), breaks=[(time(12), time(13))])
+    /// ```
+    #[new]
+    #[pyo3(signature=(start=None, end=None, breaks=None, preholiday_reduction=None))]
+    fn new(
+        start: Option<NaiveTime>,
+        end: Option<NaiveTime>,
+        breaks: Option<Vec<(NaiveTime, NaiveTime)>>,
+        preholiday_reduction: Option<ChronoDuration>,
+    ) -> PyResult<Self> {
+        let default = RustWorkSchedule::default();
+        let breaks = breaks.unwrap_or_else(|| match (start, end) {
+            (None, None) => default.breaks().to_vec(),
+            _ => Vec::new(),
+        });
+        let schedule = RustWorkSchedule::new(
+            start.unwrap_or(default.start()),
+            end.unwrap_or(default.end()),
+        )
+        .and_then(|schedule| {
+            breaks
+                .into_iter()
+                .try_fold(schedule, |schedule, (start, end)| {
+                    schedule.with_break(start, end)
+                })
+        })
+        .and_then(|schedule| {
+            schedule.with_preholiday_reduction(
+                preholiday_reduction.unwrap_or(default.preholiday_reduction()),
+            )
+        });
+        match schedule {
+            Ok(schedule) => Ok(Self(schedule)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let breaks: Vec<String> = self
+            .0
+            .breaks()
+            .iter()
+            .map(|(start, end)| format!("{} - {}", start, end))
+            .collect();
+        Ok(format!(
+            "WorkSchedule(start={}, end={}, breaks=[{}], preholiday_reduction={} min)",
+            self.0.start(),
+            self.0.end(),
+            breaks.join(", "),
+            self.0.preholiday_reduction().num_minutes()
+        ))
+    }
+
+    #[getter]
+    fn start(&self) -> PyResult<NaiveTime> {
+        Ok(self.0.start())
+    }
+
+    #[getter]
+    fn end(&self) -> PyResult<NaiveTime> {
+        Ok(self.0.end())
+    }
+
+    #[getter]
+    fn breaks(&self) -> PyResult<Vec<(NaiveTime, NaiveTime)>> {
+        Ok(self.0.breaks().to_vec())
+    }
+
+    #[getter]
+    fn preholiday_reduction(&self) -> PyResult<ChronoDuration> {
+        Ok(self.0.preholiday_reduction())
+    }
+
+    /// Возвращает продолжительность рабочего времени в день указанного типа.
+    ///
+    /// # Аргументы
+    /// * `kind` - Тип дня (например, "Work", "Preholiday").
+    fn work_duration(&self, kind: &str) -> PyResult<ChronoDuration> {
+        let kind = DayKind::from_str(kind).map_err(PyErr::new::<PyValueError, _>)?;
+        Ok(self.0.work_duration(kind))
+    }
+}
+
+/// Смена графика сменности.
+#[pyclass(eq)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Shift(RustShift);
+
+#[pymethods]
+impl Shift {
+    /// Создает смену.
+    ///
+    /// # Аргументы
+    /// * `start` - Начало смены.
+    /// * `duration` - Продолжительность (`timedelta`), не более суток.
+    ///   Смена может заканчиваться на следующий день.
+    #[new]
+    fn new(start: NaiveTime, duration: ChronoDuration) -> PyResult<Self> {
+        match RustShift::new(start, duration) {
+            Ok(shift) => Ok(Self(shift)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "Shift(start={}, duration={} min)",
+            self.0.start(),
+            self.0.duration().num_minutes()
+        ))
+    }
+
+    #[getter]
+    fn start(&self) -> PyResult<NaiveTime> {
+        Ok(self.0.start())
+    }
+
+    #[getter]
+    fn duration(&self) -> PyResult<ChronoDuration> {
+        Ok(self.0.duration())
+    }
+
+    /// Часы смены в ночное время с 22:00 до 06:00.
+    #[getter]
+    fn night_duration(&self) -> PyResult<ChronoDuration> {
+        Ok(self.0.night_duration())
+    }
+}
+
+/// График сменности: цикл смен от даты отсчета.
+#[pyclass]
+#[derive(Clone)]
+pub struct ShiftSchedule(RustShiftSchedule);
+
+#[pymethods]
+impl ShiftSchedule {
+    /// Создает график сменности.
+    ///
+    /// # Аргументы
+    /// * `pattern` - Цикл графика: смена или `None` для выходного дня.
+    /// * `anchor` - Дата первого дня цикла.
+    ///
+    /// # Пример
+    /// ```python
+    /// day, night = Shift(time(8), timedelta(hours=12)), Shift(time(20), timedelta(hours=12))
+    /// schedule = ShiftSchedule([day, night, None, None], date(2024, 1, 1))
+    /// ```
+    #[new]
+    fn new(pattern: Vec<Option<Shift>>, anchor: NaiveDate) -> PyResult<Self> {
+        let pattern = pattern.into_iter().map(|s| s.map(|s| s.0)).collect();
+        match ShiftPattern::new(pattern) {
+            Ok(pattern) => Ok(Self(RustShiftSchedule::new(pattern, anchor))),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Создает график "`on` через `off`": 2/2, 1/3, 5/2.
+    ///
+    /// # Аргументы
+    /// * `on` - Количество рабочих дней подряд.
+    /// * `off` - Количество выходных дней подряд.
+    /// * `shift` - Смена в рабочий день.
+    /// * `anchor` - Дата первого рабочего дня цикла.
+    #[staticmethod]
+    fn on_off(on: usize, off: usize, shift: Shift, anchor: NaiveDate) -> PyResult<Self> {
+        match ShiftPattern::on_off(on, off, shift.0) {
+            Ok(pattern) => Ok(Self(RustShiftSchedule::new(pattern, anchor))),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "ShiftSchedule(cycle={} days, anchor={})",
+            self.0.pattern().cycle().len(),
+            self.0.anchor()
+        ))
+    }
+
+    #[getter]
+    fn anchor(&self) -> PyResult<NaiveDate> {
+        Ok(self.0.anchor())
+    }
+
+    #[getter]
+    fn pattern(&self) -> PyResult<Vec<Option<Shift>>> {
+        Ok(self
+            .0
+            .pattern()
+            .cycle()
+            .iter()
+            .map(|s| s.map(Shift))
+            .collect())
+    }
+
+    /// Возвращает смену, начинающуюся в указанный день, или `None`.
+    fn shift_on(&self, date: NaiveDate) -> PyResult<Option<Shift>> {
+        Ok(self.0.shift_on(date).map(Shift))
+    }
+
+    /// Возвращает график сотрудника за период: список `(день календаря, смена или None)`.
+    ///
+    /// # Аргументы
+    /// * `calendar` - Производственный календарь.
+    /// * `start` - Начальная дата включительно.
+    /// * `end` - Конечная дата включительно.
+    fn roster(
+        &self,
+        calendar: &ProductCalendar,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> PyResult<Vec<(Day, Option<Shift>)>> {
+        match self.0.roster(&calendar.0, start, end) {
+            Ok(roster) => Ok(roster
+                .into_iter()
+                .map(|d| (Day(d.day), d.shift.map(Shift)))
+                .collect()),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает итоги графика за период: смены и часы, в том числе ночные
+    /// и в нерабочие праздничные дни, и норму рабочего времени.
+    ///
+    /// # Аргументы
+    /// * `calendar` - Производственный календарь.
+    /// * `start` - Начальная дата включительно.
+    /// * `end` - Конечная дата включительно.
+    /// * `week` - Продолжительность рабочей недели для нормы: 40, 36 или 24 часа.
+    #[pyo3(signature=(calendar, start, end, week=40))]
+    fn summary(
+        &self,
+        calendar: &ProductCalendar,
+        start: NaiveDate,
+        end: NaiveDate,
+        week: u16,
+    ) -> PyResult<ShiftSummary> {
+        let map_err =
+            |e: errors::ProductCalendarError| PyErr::new::<PyValueError, _>(e.to_string());
+        let week = WeekLength::from_hours(week).map_err(map_err)?;
+        Ok(ShiftSummary(
+            self.0
+                .summary(&calendar.0, start, end, week)
+                .map_err(map_err)?,
+        ))
+    }
+}
+
+/// Итоги графика сменности за период.
+#[pyclass]
+pub struct ShiftSummary(RustShiftSummary);
+
+#[pymethods]
+impl ShiftSummary {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "ShiftSummary(shifts={}, hours={}, night_hours={}, holiday_shifts={}, holiday_hours={}, norm_hours={})",
+            self.0.shifts,
+            self.0.hours,
+            self.0.night_hours,
+            self.0.holiday_shifts,
+            self.0.holiday_hours,
+            self.0.norm_hours
+        ))
+    }
+
+    #[getter]
+    fn shifts(&self) -> PyResult<u32> {
+        Ok(self.0.shifts)
+    }
+
+    #[getter]
+    fn hours(&self) -> PyResult<f64> {
+        Ok(self.0.hours)
+    }
+
+    #[getter]
+    fn night_hours(&self) -> PyResult<f64> {
+        Ok(self.0.night_hours)
+    }
+
+    /// Смены, затрагивающие нерабочие праздничные дни.
+    #[getter]
+    fn holiday_shifts(&self) -> PyResult<u32> {
+        Ok(self.0.holiday_shifts)
+    }
+
+    /// Часы в нерабочие праздничные дни, оплачиваемые в двойном размере.
+    #[getter]
+    fn holiday_hours(&self) -> PyResult<f64> {
+        Ok(self.0.holiday_hours)
+    }
+
+    #[getter]
+    fn norm_hours(&self) -> PyResult<f64> {
+        Ok(self.0.norm_hours)
+    }
+
+    /// Отклонение от нормы: переработка при положительном значении.
+    fn deviation(&self) -> PyResult<f64> {
+        Ok(self.0.deviation())
+    }
+}
+
+/// Итог учетного периода при суммированном учете рабочего времени.
+#[pyclass]
+pub struct PeriodBalance(RustPeriodBalance);
+
+#[pymethods]
+impl PeriodBalance {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "PeriodBalance(period={}, worked_hours={}, norm_hours={})",
+            self.0.period, self.0.worked_hours, self.0.norm_hours
+        ))
+    }
+
+    #[getter]
+    fn period(&self) -> PyResult<String> {
+        Ok(self.0.period.to_string())
+    }
+
+    #[getter]
+    fn start(&self) -> PyResult<NaiveDate> {
+        Ok(self.0.start)
+    }
+
+    #[getter]
+    fn end(&self) -> PyResult<NaiveDate> {
+        Ok(self.0.end)
+    }
+
+    #[getter]
+    fn worked_hours(&self) -> PyResult<f64> {
+        Ok(self.0.worked_hours)
+    }
+
+    #[getter]
+    fn norm_hours(&self) -> PyResult<f64> {
+        Ok(self.0.norm_hours)
+    }
+
+    /// Отклонение от нормы: переработка при положительном значении.
+    fn balance(&self) -> PyResult<f64> {
+        Ok(self.0.balance())
+    }
+
+    /// Сверхурочная работа за учетный период.
+    fn overtime(&self) -> PyResult<f64> {
+        Ok(self.0.overtime())
+    }
+
+    /// Недоработка до нормы за учетный период.
+    fn undertime(&self) -> PyResult<f64> {
+        Ok(self.0.undertime())
+    }
+}
+
+/// Личный календарь сотрудника с периодами отсутствия.
+#[pyclass]
+#[derive(Clone)]
+pub struct EmployeeCalendar(RustEmployeeCalendar);
+
+#[pymethods]
+impl EmployeeCalendar {
+    /// Создает личный календарь сотрудника.
+    ///
+    /// # Аргументы
+    /// * `calendar` - Производственный календарь.
+    /// * `absences` - Периоды отсутствия `[(AbsenceKind, начало, окончание)]`, даты включительно.
+    ///
+    /// # Пример
+    /// ```python
+    /// employee = EmployeeCalendar(calendar, [(AbsenceKind.Vacation, date(2024, 5, 6), date(2024, 5, 19))])
+    /// ```
+    #[new]
+    #[pyo3(signature=(calendar, absences=None))]
+    fn new(
+        calendar: &ProductCalendar,
+        absences: Option<Vec<(AbsenceKind, NaiveDate, NaiveDate)>>,
+    ) -> PyResult<Self> {
+        let mut employee = Self(RustEmployeeCalendar::new(calendar.0.clone()));
+        for (kind, start, end) in absences.unwrap_or_default() {
+            employee.add_absence(kind, start, end)?;
+        }
+        Ok(employee)
+    }
+
+    /// Добавляет период отсутствия. Периоды не должны пересекаться.
+    fn add_absence(&mut self, kind: AbsenceKind, start: NaiveDate, end: NaiveDate) -> PyResult<()> {
+        self.0
+            .add_absence(kind.into(), start, end)
+            .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))
+    }
+
+    #[getter]
+    fn absences(&self) -> PyResult<Vec<(AbsenceKind, NaiveDate, NaiveDate)>> {
+        Ok(self
+            .0
+            .absences()
+            .iter()
+            .map(|a| (a.kind.into(), a.start, a.end))
+            .collect())
+    }
+
+    #[getter]
+    fn calendar(&self) -> PyResult<ProductCalendar> {
+        Ok(ProductCalendar(self.0.calendar().clone()))
+    }
+
+    /// Возвращает причину отсутствия в указанный день или `None`.
+    fn absence_on(&self, date: NaiveDate) -> PyResult<Option<AbsenceKind>> {
+        Ok(self.0.absence_on(date).map(AbsenceKind::from))
+    }
+
+    /// Возвращает дни за период: список `(день календаря, причина отсутствия или None)`.
+    fn days_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> PyResult<Vec<(Day, Option<AbsenceKind>)>> {
+        match self.0.days_between(start, end) {
+            Ok(days) => Ok(days
+                .into_iter()
+                .map(|d| (Day(d.day), d.absence.map(AbsenceKind::from)))
+                .collect()),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает статистику личного календаря за период включительно.
+    fn statistic_between(&self, start: NaiveDate, end: NaiveDate) -> PyResult<EmployeeStatistic> {
+        match self.0.statistic_between(start, end) {
+            Ok(statistic) => Ok(EmployeeStatistic(statistic)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+
+    /// Возвращает количество отработанных дней за период включительно.
+    /// Дни командировки считаются отработанными.
+    fn worked_days_between(&self, start: NaiveDate, end: NaiveDate) -> PyResult<u16> {
+        Ok(self.statistic_between(start, end)?.0.worked_days())
+    }
+
+    /// Возвращает отработанные часы за период включительно по норме рабочей недели.
+    ///
+    /// # Аргументы
+    /// * `week` - Продолжительность рабочей недели в часах: 40, 36 или 24.
+    #[pyo3(signature=(start, end, week=40))]
+    fn worked_hours_between(&self, start: NaiveDate, end: NaiveDate, week: u16) -> PyResult<f64> {
+        self.statistic_between(start, end)?.worked_hours(week)
+    }
+}
+
+/// Статистика личного календаря за период.
+#[pyclass]
+pub struct EmployeeStatistic(RustEmployeeStatistic);
+
+#[pymethods]
+impl EmployeeStatistic {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "EmployeeStatistic(worked_days={}, vacation={}, sick_leave={}, business_trip={}, unpaid_leave={})",
+            self.0.worked_days(),
+            self.0.vacation,
+            self.0.sick_leave,
+            self.0.business_trip,
+            self.0.unpaid_leave
+        ))
+    }
+
+    /// Статистика производственного календаря за период.
+    #[getter]
+    fn calendar(&self) -> PyResult<Statistic> {
+        Ok(Statistic(self.0.calendar))
+    }
+
+    /// Отработанные рабочие и предпраздничные дни.
+    #[getter]
+    fn worked(&self) -> PyResult<Statistic> {
+        Ok(Statistic(self.0.worked))
+    }
+
+    /// Календарные дни отпуска без нерабочих праздничных дней.
+    #[getter]
+    fn vacation(&self) -> PyResult<u16> {
+        Ok(self.0.vacation)
+    }
+
+    #[getter]
+    fn sick_leave(&self) -> PyResult<u16> {
+        Ok(self.0.sick_leave)
+    }
+
+    #[getter]
+    fn business_trip(&self) -> PyResult<u16> {
+        Ok(self.0.business_trip)
+    }
+
+    #[getter]
+    fn unpaid_leave(&self) -> PyResult<u16> {
+        Ok(self.0.unpaid_leave)
+    }
+
+    /// Возвращает количество дней отсутствия указанного типа.
+    fn absences(&self, kind: AbsenceKind) -> PyResult<u16> {
+        Ok(self.0.absences(kind.into()))
+    }
+
+    fn worked_days(&self) -> PyResult<u16> {
+        Ok(self.0.worked_days())
+    }
+
+    /// Возвращает отработанные часы по норме для рабочей недели `week`: 40, 36 или 24 часа.
+    #[pyo3(signature=(week=40))]
+    fn worked_hours(&self, week: u16) -> PyResult<f64> {
+        match WeekLength::from_hours(week) {
+            Ok(week) => Ok(self.0.worked_hours(week)),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
+        }
+    }
+}
+
+/// Корпоративные изменения поверх официального календаря.
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct Overlay(RustOverlay);
+
+#[pymethods]
+impl Overlay {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Загружает изменения из json файла.
+    #[staticmethod]
+    fn load(path: PathBuf) -> PyResult<Self> {
+        match RustOverlay::load(path) {
+            Ok(overlay) => Ok(Self(overlay)),
+            Err(e) => Err(PyErr::new::<PyIOError, _>(e.to_string())),
+        }
+    }
+
+    /// Сохраняет изменения в json файл.
+    fn save(&self, path: PathBuf) -> PyResult<()> {
+        self.0
+            .save(path)
+            .map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))
+    }
+
+    /// Добавляет изменение типа дня, заменяя существующее на ту же дату.
+    ///
+    /// # Аргументы
+    /// * `date` - Дата.
+    /// * `kind` - Тип дня (например, "Work", "Holiday").
+    /// * `reason` - Причина изменения.
+    #[pyo3(signature=(date, kind, reason=""))]
+    fn add(&mut self, date: NaiveDate, kind: &str, reason: &str) -> PyResult<()> {
+        let kind = DayKind::from_str(kind).map_err(PyErr::new::<PyValueError, _>)?;
+        self.0.add(date, kind, reason);
+        Ok(())
+    }
+
+    /// Удаляет изменение на указанную дату.
+    fn remove(&mut self, date: NaiveDate) -> PyResult<bool> {
+        Ok(self.0.remove(date).is_some())
+    }
+
+    /// Возвращает причину изменения на указанную дату.
+    fn reason(&self, date: NaiveDate) -> PyResult<Option<String>> {
+        Ok(self.0.get(date).map(|o| o.reason.clone()))
+    }
+
+    /// Возвращает изменения в виде списка словарей.
+    fn as_list<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.0
+            .iter()
+            .map(|o| {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("date", o.date)?;
+                dict.set_item("kind", o.kind.to_string())?;
+                dict.set_item("reason", &o.reason)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Результат расчета срока по ст. 191-193 ГК РФ и ст. 6.1 НК РФ: дата начала,
+/// дата истечения без учета переноса (`nominal_end`), последний день срока
+/// с учетом переноса на рабочий день (`end`) и пояснение расчета.
+/// Создается методом `ProductCalendar.deadline`.
+#[pyclass]
+pub struct Deadline(RustDeadline);
+
+#[pymethods]
+impl Deadline {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "{} ({})",
+            self.0.end.get_date(),
+            self.0.explanation
+        ))
+    }
+
+    #[getter]
+    fn start(&self) -> PyResult<NaiveDate> {
+        Ok(self.0.start)
+    }
+
+    #[getter]
+    fn nominal_end(&self) -> PyResult<NaiveDate> {
+        Ok(self.0.nominal_end)
+    }
+
+    #[getter]
+    fn end(&self) -> PyResult<Day> {
+        Ok(Day(self.0.end.clone()))
+    }
+
+    #[getter]
+    fn shifted(&self) -> PyResult<bool> {
+        Ok(self.0.is_shifted())
+    }
+
+    #[getter]
+    fn explanation(&self) -> PyResult<String> {
+        Ok(self.0.explanation.clone())
+    }
+}
+
+///В python экземпляр этого класса нельзя создать напрямую
+/// т.к. некорректно будет проставлен DayKind
+#[pyclass]
+pub struct Day(RustDay);
+
+#[pymethods]
+impl Day {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{}", self.0))
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+        match op {
+            CompareOp::Eq => Ok(self.0 == other.0),
+            _ => Ok(false),
+        }
+    }
+
+    fn ordinal(&self) -> PyResult<u32> {
+        Ok(self.0.ordinal())
+    }
+
+    #[getter]
+    fn weekday(&self) -> PyResult<String> {
+        Ok(self.0.get_weekday().to_string())
+    }
+
+    #[getter]
+    fn day(&self, py: Python<'_>) -> PyObject {
+        self.0.get_date().into_py(py)
+    }
+
+    #[getter]
+    fn kind(&self) -> PyResult<String> {
+        Ok(self.0.get_kind().to_string())
+    }
+
+    #[getter]
+    fn predicted(&self) -> PyResult<bool> {
+        Ok(self.0.is_predicted())
+    }
+
+    /// Название праздника или причина изменения типа дня.
+    #[getter]
+    fn name(&self) -> PyResult<Option<String>> {
+        Ok(self.0.get_name().map(str::to_owned))
+    }
+
+    /// Дата, с которой выходной перенесен на этот день.
+    #[getter]
+    fn transferred_from(&self) -> PyResult<Option<NaiveDate>> {
+        Ok(self.0.get_transferred_from())
+    }
+
+    /// Дата, на которую перенесен выходной с этого дня.
+    #[getter]
+    fn transferred_to(&self) -> PyResult<Option<NaiveDate>> {
+        Ok(self.0.get_transferred_to())
+    }
+
+    //TODO: В Отдельный трейт
+    fn as_dict<'py>(&self, py: Python<'py>) -> Bound<'py, PyDict> {
+        self.0.as_map().into_py_dict_bound(py)
+    }
+}
+
+/// Настраивает кэш календарей.
+///
+/// # Аргументы
+/// * `dir` - Каталог для хранения календарей на диске. `None` - только в памяти процесса.
+/// * `ttl` - Время жизни записи в секундах, после которого календарь
+///   загружается из источника заново. `None` - записи не устаревают.
+///
+/// # Пример
+/// ```
+/// from product_calendar import configure_cache
+///
+///
+/// configure_cache("/var/cache/product_calendar", ttl=24 * 60 * 60)
+/// ```
+#[pyfunction]
+#[pyo3(signature=(dir=None, ttl=None))]
+fn configure_cache(dir: Option<PathBuf>, ttl: Option<f64>) -> PyResult<()> {
+    let ttl = ttl
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .map_err(|e| PyErr::new::<PyValueError, _>(e.to_string()))?;
+    cache::configure(cache::CacheConfig { dir, ttl });
+    Ok(())
+}
+
+/// Удаляет календарь за указанный год из кэша в памяти и на диске.
+#[pyfunction]
+fn invalidate_cache(year: u16) -> PyResult<()> {
+    cache::invalidate(year).map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))
+}
+
+/// Полностью очищает кэш календарей в памяти и на диске.
+#[pyfunction]
+fn clear_cache() -> PyResult<()> {
+    cache::clear().map_err(|e| PyErr::new::<PyIOError, _>(e.to_string()))
+}
+
+/// Возвращает годы, данные которых встроены в библиотеку.
+#[cfg(feature = "bundled")]
+#[pyfunction]
+fn bundled_years() -> Vec<u16> {
+    source::BundledSource::years()
+}
+
+#[pymodule]
+fn product_calendar(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    #[cfg(feature = "bundled")]
+    m.add_function(wrap_pyfunction!(bundled_years, m)?)?;
+    m.add_function(wrap_pyfunction!(configure_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(invalidate_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(clear_cache, m)?)?;
+    m.add(
+        "NormMismatchError",
+        py.get_type_bound::<NormMismatchError>(),
+    )?;
+    m.add_class::<ProductCalendar>()?;
+    m.add_class::<Statistic>()?;
+    m.add_class::<Day>()?;
+    m.add_class::<Overlay>()?;
+    m.add_class::<BusinessDayConvention>()?;
+    m.add_class::<PeriodUnit>()?;
+    m.add_class::<Grouping>()?;
+    m.add_class::<WorkSchedule>()?;
+    m.add_class::<Shift>()?;
+    m.add_class::<ShiftSchedule>()?;
+    m.add_class::<ShiftSummary>()?;
+    m.add_class::<PeriodBalance>()?;
+    m.add_class::<AbsenceKind>()?;
+    m.add_class::<EmployeeCalendar>()?;
+    m.add_class::<EmployeeStatistic>()?;
+    m.add_class::<Deadline>()?;
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::day::{kind::DayKind, Day};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+//Даты мусульманских праздников по лунному календарю, объявляемые ДУМ: (год, месяц, день)
+const URAZA_BAYRAM: [(i32, u32, u32); 5] = [
+    (2022, 5, 2),
+    (2023, 4, 21),
+    (2024, 4, 10),
+    (2025, 3, 30),
+    (2026, 3, 20),
+];
+const KURBAN_BAYRAM: [(i32, u32, u32); 5] = [
+    (2022, 7, 9),
+    (2023, 6, 28),
+    (2024, 6, 16),
+    (2025, 6, 6),
+    (2026, 5, 27),
+];
+
+/// Субъект РФ с собственными нерабочими праздничными днями.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    Tatarstan,
+    Bashkortostan,
+    Chechnya,
+    Yakutia,
+    Adygea,
+    Dagestan,
+    Ingushetia,
+}
+
+impl Region {
+    //Фиксированные региональные праздники: (месяц, день, название)
+    fn fixed_holidays(&self) -> &'static [(u32, u32, &'static str)] {
+        match self {
+            Region::Tatarstan => &[
+                (8, 30, "День Республики Татарстан"),
+                (11, 6, "День Конституции Республики Татарстан"),
+            ],
+            Region::Bashkortostan => &[
+                (10, 11, "День Республики Башкортостан"),
+                (12, 24, "День Конституции Республики Башкортостан"),
+            ],
+            Region::Chechnya => &[(4, 16, "День мира в Чеченской Республике")],
+            Region::Yakutia => &[(4, 27, "День Республики Саха (Якутия)"), (6, 21, "Ысыах")],
+            Region::Adygea => &[(10, 5, "День образования Республики Адыгея")],
+            Region::Dagestan => &[],
+            Region::Ingushetia => &[(6, 4, "День образования Республики Ингушетия")],
+        }
+    }
+
+    fn observes_islamic_holidays(&self) -> bool {
+        !matches!(self, Region::Yakutia)
+    }
+
+    /// Региональные нерабочие праздничные дни за год.
+    ///
+    /// Ураза-байрам и Курбан-байрам известны только для лет из встроенной таблицы.
+    pub fn holidays(&self, year: i32) -> Vec<(NaiveDate, &'static str)> {
+        let mut holidays: Vec<(NaiveDate, &'static str)> = self
+            .fixed_holidays()
+            .iter()
+            .filter_map(|&(month, day, name)| {
+                NaiveDate::from_ymd_opt(year, month, day).map(|d| (d, name))
+            })
+            .collect();
+
+        if self.observes_islamic_holidays() {
+            for (table, name) in [
+                (&URAZA_BAYRAM, "Ураза-байрам"),
+                (&KURBAN_BAYRAM, "Курбан-байрам"),
+            ] {
+                holidays.extend(
+                    table
+                        .iter()
+                        .filter(|(y, _, _)| *y == year)
+                        .filter_map(|&(y, m, d)| NaiveDate::from_ymd_opt(y, m, d))
+                        .map(|d| (d, name)),
+                );
+            }
+        }
+        holidays.sort();
+        holidays
+    }
+
+    /// Отмечает региональные праздники в днях календаря (отсортированных по дате).
+    ///
+    /// Праздник в рабочий день становится нерабочим, а предшествующий
+    /// ему рабочий день - предпраздничным. Праздники в выходные не переносятся.
+    pub fn apply(&self, days: &mut [Day]) {
+        let (first, last) = match (days.first(), days.last()) {
+            (Some(first), Some(last)) => (first.get_year(), last.get_year()),
+            _ => return,
+        };
+
+        for year in first..=last {
+            for (date, name) in self.holidays(year) {
+                let Ok(idx) = days.binary_search_by_key(&date, |d| d.get_date()) else {
+                    continue;
+                };
+                days[idx].set_name(Some(name.to_owned()));
+                if !matches!(days[idx].get_kind(), DayKind::Work | DayKind::Preholiday) {
+                    continue;
+                }
+                days[idx].set_kind(DayKind::Holiday);
+                if idx > 0 && days[idx - 1].get_kind() == DayKind::Work {
+                    days[idx - 1].set_kind(DayKind::Preholiday);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Region::Tatarstan => "Tatarstan",
+            Region::Bashkortostan => "Bashkortostan",
+            Region::Chechnya => "Chechnya",
+            Region::Yakutia => "Yakutia",
+            Region::Adygea => "Adygea",
+            Region::Dagestan => "Dagestan",
+            Region::Ingushetia => "Ingushetia",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Tatarstan" => Ok(Region::Tatarstan),
+            "Bashkortostan" => Ok(Region::Bashkortostan),
+            "Chechnya" => Ok(Region::Chechnya),
+            "Yakutia" => Ok(Region::Yakutia),
+            "Adygea" => Ok(Region::Adygea),
+            "Dagestan" => Ok(Region::Dagestan),
+            "Ingushetia" => Ok(Region::Ingushetia),
+            _ => Err(format!("Invalid Region: '{}'. Available options: 'Tatarstan', 'Bashkortostan', 'Chechnya', 'Yakutia', 'Adygea', 'Dagestan', 'Ingushetia'", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pc::get_product_calendar;
+
+    #[test]
+    fn test_tatarstan_2024() {
+        let pc = get_product_calendar(Some(2024))
+            .unwrap()
+            .with_region(Region::Tatarstan);
+        let statistic = pc.statistic();
+        assert_eq!(statistic.holidays, 20);
+        assert_eq!(statistic.preholidays, 8);
+        assert_eq!(statistic.work_days + statistic.preholidays, 245);
+
+        let kind = |m, d| {
+            pc.info_by_date(NaiveDate::from_ymd_opt(2024, m, d).unwrap())
+                .unwrap()
+                .get_kind()
+        };
+        assert_eq!(kind(4, 10), DayKind::Holiday);
+        assert_eq!(kind(4, 9), DayKind::Preholiday);
+        //Курбан-байрам в воскресенье не переносится
+        assert_eq!(kind(6, 16), DayKind::Weekend);
+        assert_eq!(kind(6, 17), DayKind::Work);
+    }
+}
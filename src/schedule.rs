@@ -0,0 +1,447 @@
+use crate::day::kind::DayKind;
+use crate::errors::ProductCalendarError;
+use crate::pc::ProductCalendar;
+use chrono::{DateTime, Duration, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Распорядок рабочего дня: начало и конец, перерывы и сокращение предпраздничного дня.
+/// Выходные и праздничные дни нерабочие целиком.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkSchedule {
+    start: NaiveTime,
+    end: NaiveTime,
+    //Отсортированы по началу, не пересекаются и лежат внутри рабочего дня
+    breaks: Vec<(NaiveTime, NaiveTime)>,
+    //Сокращается конец дня (ст. 95 ТК РФ)
+    preholiday_reduction: Duration,
+}
+
+//09:00 - 18:00 с обедом 13:00 - 14:00, в предпраздничный день до 17:00
+impl Default for WorkSchedule {
+    fn default() -> Self {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        Self {
+            start: time(9),
+            end: time(18),
+            breaks: vec![(time(13), time(14))],
+            preholiday_reduction: Duration::hours(1),
+        }
+    }
+}
+
+impl WorkSchedule {
+    /// Распорядок без перерывов с сокращением предпраздничного дня на час.
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Result<Self, ProductCalendarError> {
+        if start >= end {
+            return Err(ProductCalendarError::ScheduleError(format!(
+                "начало дня {} не раньше конца {}",
+                start, end
+            )));
+        }
+        //Рабочий день не длиннее часа не сокращается
+        let preholiday_reduction = if end - start > Duration::hours(1) {
+            Duration::hours(1)
+        } else {
+            Duration::zero()
+        };
+        Ok(Self {
+            start,
+            end,
+            breaks: Vec::new(),
+            preholiday_reduction,
+        })
+    }
+
+    pub fn with_break(
+        mut self,
+        start: NaiveTime,
+        end: NaiveTime,
+    ) -> Result<Self, ProductCalendarError> {
+        if start >= end || start <= self.start || end >= self.end {
+            return Err(ProductCalendarError::ScheduleError(format!(
+                "перерыв {} - {} должен лежать внутри рабочего дня {} - {}",
+                start, end, self.start, self.end
+            )));
+        }
+        if self.breaks.iter().any(|(s, e)| start < *e && *s < end) {
+            return Err(ProductCalendarError::ScheduleError(format!(
+                "перерыв {} - {} пересекается с другим перерывом",
+                start, end
+            )));
+        }
+        self.breaks.push((start, end));
+        self.breaks.sort();
+        //Перерыв мог сделать день короче сокращения
+        let reduction = self.preholiday_reduction;
+        self.with_preholiday_reduction(reduction)
+    }
+
+    pub fn with_preholiday_reduction(
+        mut self,
+        reduction: Duration,
+    ) -> Result<Self, ProductCalendarError> {
+        if reduction < Duration::zero() || reduction >= self.work_duration(DayKind::Work) {
+            return Err(ProductCalendarError::ScheduleError(format!(
+                "сокращение предпраздничного дня на {} мин. не меньше продолжительности дня",
+                reduction.num_minutes()
+            )));
+        }
+        self.preholiday_reduction = reduction;
+        Ok(self)
+    }
+
+    pub fn start(&self) -> NaiveTime {
+        self.start
+    }
+
+    pub fn end(&self) -> NaiveTime {
+        self.end
+    }
+
+    pub fn breaks(&self) -> &[(NaiveTime, NaiveTime)] {
+        &self.breaks
+    }
+
+    pub fn preholiday_reduction(&self) -> Duration {
+        self.preholiday_reduction
+    }
+
+    /// Рабочие интервалы дня вида `kind` в порядке следования.
+    pub fn intervals(&self, kind: DayKind) -> Vec<(NaiveTime, NaiveTime)> {
+        if matches!(kind, DayKind::Holiday | DayKind::Weekend) {
+            return Vec::new();
+        }
+
+        let mut intervals = Vec::with_capacity(self.breaks.len() + 1);
+        let mut cursor = self.start;
+        for (start, end) in &self.breaks {
+            intervals.push((cursor, *start));
+            cursor = *end;
+        }
+        intervals.push((cursor, self.end));
+
+        if kind == DayKind::Preholiday {
+            let mut reduction = self.preholiday_reduction;
+            while let Some((start, end)) = intervals.last_mut() {
+                if *end - *start > reduction {
+                    *end -= reduction;
+                    break;
+                }
+                reduction -= *end - *start;
+                intervals.pop();
+            }
+        }
+        intervals
+    }
+
+    /// Продолжительность рабочего времени в день вида `kind`.
+    pub fn work_duration(&self, kind: DayKind) -> Duration {
+        self.intervals(kind)
+            .iter()
+            .fold(Duration::zero(), |total, (start, end)| {
+                total + (*end - *start)
+            })
+    }
+
+    //Рабочее время дня вида `kind` с `from` по `to`
+    fn worked(&self, kind: DayKind, from: NaiveTime, to: NaiveTime) -> Duration {
+        self.intervals(kind)
+            .iter()
+            .map(|(start, end)| (*end).min(to) - (*start).max(from))
+            .filter(|d| *d > Duration::zero())
+            .fold(Duration::zero(), |total, d| total + d)
+    }
+
+    /// Рабочее время между `from` и `to`. Полные дни внутри промежутка
+    /// считаются по статистике календаря, без перебора дней.
+    /// Если `to` раньше `from`, результат отрицательный.
+    pub fn elapsed(
+        &self,
+        pc: &ProductCalendar,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Duration, ProductCalendarError> {
+        if from > to {
+            return Ok(-self.elapsed(pc, to, from)?);
+        }
+
+        let first = pc.day_at(from.date())?;
+        if from.date() == to.date() {
+            return Ok(self.worked(first.get_kind(), from.time(), to.time()));
+        }
+        let last = pc.day_at(to.date())?;
+        let mut total = self.worked(first.get_kind(), from.time(), self.end)
+            + self.worked(last.get_kind(), NaiveTime::MIN, to.time());
+
+        if let (Some(start), Some(end)) = (from.date().succ_opt(), to.date().pred_opt()) {
+            if start <= end {
+                let statistic = pc.statistic_between(start, end)?;
+                total += self.work_duration(DayKind::Work) * statistic.work_days as i32
+                    + self.work_duration(DayKind::Preholiday) * statistic.preholidays as i32;
+            }
+        }
+        Ok(total)
+    }
+
+    /// Момент, наступающий через `amount` рабочего времени после `from`
+    /// (или до него при отрицательном `amount`).
+    pub fn add(
+        &self,
+        pc: &ProductCalendar,
+        from: NaiveDateTime,
+        amount: Duration,
+    ) -> Result<NaiveDateTime, ProductCalendarError> {
+        let out_of_range = || ProductCalendarError::DateOutOfRange(from.to_string());
+        if amount == Duration::zero() {
+            return Ok(from);
+        }
+
+        let mut remaining = amount.abs();
+        if amount > Duration::zero() {
+            for day in pc.forward_from(from.date()) {
+                for (start, end) in self.intervals(day.get_kind()) {
+                    let start = if day.get_date() == from.date() {
+                        start.max(from.time())
+                    } else {
+                        start
+                    };
+                    if end <= start {
+                        continue;
+                    }
+                    if end - start >= remaining {
+                        return Ok(day.get_date().and_time(start + remaining));
+                    }
+                    remaining -= end - start;
+                }
+            }
+        } else {
+            for day in pc.backward_from(from.date()) {
+                for (start, end) in self.intervals(day.get_kind()).into_iter().rev() {
+                    let end = if day.get_date() == from.date() {
+                        end.min(from.time())
+                    } else {
+                        end
+                    };
+                    if end <= start {
+                        continue;
+                    }
+                    if end - start >= remaining {
+                        return Ok(day.get_date().and_time(end - remaining));
+                    }
+                    remaining -= end - start;
+                }
+            }
+        }
+        Err(out_of_range())
+    }
+
+    /// Рабочее время между моментами `from` и `to` по местному времени `time_zone`
+    /// (по умолчанию - часового пояса календаря): рабочий день и даты праздников
+    /// определяются в этом часовом поясе.
+    pub fn elapsed_utc(
+        &self,
+        pc: &ProductCalendar,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        time_zone: Option<Tz>,
+    ) -> Result<Duration, ProductCalendarError> {
+        let time_zone = time_zone.unwrap_or(pc.time_zone());
+        self.elapsed(
+            pc,
+            from.with_timezone(&time_zone).naive_local(),
+            to.with_timezone(&time_zone).naive_local(),
+        )
+    }
+
+    /// Момент, наступающий через `amount` рабочего времени после `from`,
+    /// по местному времени `time_zone` (по умолчанию - часового пояса календаря).
+    pub fn add_utc(
+        &self,
+        pc: &ProductCalendar,
+        from: DateTime<Utc>,
+        amount: Duration,
+        time_zone: Option<Tz>,
+    ) -> Result<DateTime<Utc>, ProductCalendarError> {
+        let time_zone = time_zone.unwrap_or(pc.time_zone());
+        let local = self.add(pc, from.with_timezone(&time_zone).naive_local(), amount)?;
+        //При переводе часов берется более раннее из неоднозначных времен
+        time_zone
+            .from_local_datetime(&local)
+            .earliest()
+            .map(|moment| moment.with_timezone(&Utc))
+            .ok_or_else(|| ProductCalendarError::DateOutOfRange(local.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pc::get_product_calendar;
+    use chrono::NaiveDate;
+
+    fn at(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_intervals() {
+        let schedule = WorkSchedule::default();
+        assert_eq!(schedule.work_duration(DayKind::Work), Duration::hours(8));
+        assert_eq!(
+            schedule.work_duration(DayKind::Preholiday),
+            Duration::hours(7)
+        );
+        assert_eq!(schedule.work_duration(DayKind::Weekend), Duration::zero());
+
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        //Сокращение на 5 часов съедает вторую половину дня и задевает первую
+        let schedule = WorkSchedule::default()
+            .with_preholiday_reduction(Duration::hours(5))
+            .unwrap();
+        assert_eq!(
+            schedule.intervals(DayKind::Preholiday),
+            vec![(time(9), time(12))]
+        );
+        assert!(WorkSchedule::default()
+            .with_break(time(12), time(14))
+            .is_err());
+        assert!(WorkSchedule::new(time(18), time(9)).is_err());
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        let schedule = WorkSchedule::default();
+
+        //Внутри дня с обедом
+        assert_eq!(
+            schedule
+                .elapsed(&pc, at(5, 6, 10, 0), at(5, 6, 15, 0))
+                .unwrap(),
+            Duration::hours(4)
+        );
+        //Пятница 16:00 - понедельник 10:00
+        assert_eq!(
+            schedule
+                .elapsed(&pc, at(5, 17, 16, 0), at(5, 20, 10, 0))
+                .unwrap(),
+            Duration::hours(3)
+        );
+        //Через майские праздники: 8 мая - предпраздничный, 9 и 10 - праздники
+        assert_eq!(
+            schedule
+                .elapsed(&pc, at(5, 7, 9, 0), at(5, 13, 18, 0))
+                .unwrap(),
+            Duration::hours(8 + 7 + 8)
+        );
+        assert_eq!(
+            schedule
+                .elapsed(&pc, at(5, 13, 18, 0), at(5, 7, 9, 0))
+                .unwrap(),
+            -Duration::hours(23)
+        );
+        //Месяц целиком совпадает с нормой
+        assert_eq!(
+            schedule
+                .elapsed(&pc, at(5, 1, 0, 0), at(5, 31, 23, 0))
+                .unwrap(),
+            Duration::hours(
+                pc.extract_dates_in_month(5)
+                    .unwrap()
+                    .statistic()
+                    .work_hours() as i64
+            )
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        let schedule = WorkSchedule::default();
+
+        assert_eq!(
+            schedule
+                .add(&pc, at(5, 6, 12, 0), Duration::hours(2))
+                .unwrap(),
+            at(5, 6, 15, 0)
+        );
+        //8 мая - предпраздничный день до 17:00, дальше праздники и выходные
+        assert_eq!(
+            schedule
+                .add(&pc, at(5, 8, 16, 0), Duration::hours(2))
+                .unwrap(),
+            at(5, 13, 10, 0)
+        );
+        assert_eq!(
+            schedule
+                .add(&pc, at(5, 13, 10, 0), -Duration::hours(2))
+                .unwrap(),
+            at(5, 8, 16, 0)
+        );
+        //Выходной: отсчет с начала следующего рабочего дня
+        assert_eq!(
+            schedule
+                .add(&pc, at(5, 18, 12, 0), Duration::minutes(30))
+                .unwrap(),
+            at(5, 20, 9, 30)
+        );
+        let from = at(12, 27, 17, 0);
+        let to = schedule.add(&pc, from, Duration::hours(20)).unwrap();
+        assert_eq!(
+            schedule.elapsed(&pc, from, to).unwrap(),
+            Duration::hours(20)
+        );
+    }
+
+    #[test]
+    fn test_time_zones() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        let schedule = WorkSchedule::default();
+        let utc = |day, hour| Utc.with_ymd_and_hms(2024, 5, day, hour, 0, 0).unwrap();
+
+        //14:00 UTC - 17:00 в Москве и 00:00 следующего дня во Владивостоке (UTC+10)
+        let opened = utc(6, 14);
+        let vladivostok = pc.with_time_zone(Tz::Asia__Vladivostok);
+        assert_eq!(
+            schedule
+                .add_utc(&pc, opened, Duration::hours(2), None)
+                .unwrap(),
+            utc(7, 7)
+        );
+        assert_eq!(
+            schedule
+                .add_utc(&vladivostok, opened, Duration::hours(2), None)
+                .unwrap(),
+            utc(7, 1)
+        );
+        //Часовой пояс запроса имеет приоритет над поясом календаря
+        assert_eq!(
+            schedule
+                .add_utc(
+                    &vladivostok,
+                    opened,
+                    Duration::hours(2),
+                    Some(Tz::Europe__Moscow)
+                )
+                .unwrap(),
+            utc(7, 7)
+        );
+
+        //8 мая 23:00 UTC - уже праздник 9 мая в Москве
+        assert_eq!(
+            schedule
+                .elapsed_utc(&pc, utc(8, 13), utc(8, 23), None)
+                .unwrap(),
+            Duration::hours(1)
+        );
+        assert_eq!(
+            schedule
+                .elapsed_utc(&pc, utc(8, 13), utc(8, 23), Some(Tz::Europe__Kaliningrad))
+                .unwrap(),
+            Duration::hours(2)
+        );
+    }
+}
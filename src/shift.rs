@@ -0,0 +1,309 @@
+use crate::day::Day;
+use crate::errors::ProductCalendarError;
+use crate::pc::ProductCalendar;
+use crate::statistic::WeekLength;
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+//Ночное время (ст. 96 ТК РФ)
+const NIGHT_START: u32 = 22;
+const NIGHT_END: u32 = 6;
+
+fn hours(duration: Duration) -> f64 {
+    duration.num_minutes() as f64 / 60.0
+}
+
+fn overlap(a: (NaiveDateTime, NaiveDateTime), b: (NaiveDateTime, NaiveDateTime)) -> Duration {
+    (a.1.min(b.1) - a.0.max(b.0)).max(Duration::zero())
+}
+
+/// Смена: начало и продолжительность. Смена может заканчиваться на следующий день.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shift {
+    start: NaiveTime,
+    duration: Duration,
+}
+
+impl Shift {
+    pub fn new(start: NaiveTime, duration: Duration) -> Result<Self, ProductCalendarError> {
+        if duration <= Duration::zero() || duration > Duration::hours(24) {
+            return Err(ProductCalendarError::ScheduleError(format!(
+                "продолжительность смены {} мин. должна быть от 0 до 24 часов",
+                duration.num_minutes()
+            )));
+        }
+        Ok(Self { start, duration })
+    }
+
+    pub fn start(&self) -> NaiveTime {
+        self.start
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    //Начало и конец смены, начинающейся в день `date`
+    fn bounds(&self, date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        let start = date.and_time(self.start);
+        (start, start + self.duration)
+    }
+
+    /// Часы смены, приходящиеся на ночное время с 22:00 до 06:00.
+    pub fn night_duration(&self) -> Duration {
+        //Смена не длиннее суток затрагивает не более трех ночей: накануне, в день начала
+        //и следующую, если начинается после 22:00
+        let date = NaiveDate::default();
+        let shift = self.bounds(date);
+        [date.pred_opt(), Some(date), date.succ_opt()]
+            .into_iter()
+            .flatten()
+            .map(|night| {
+                let start = night.and_hms_opt(NIGHT_START, 0, 0).unwrap();
+                let end = night
+                    .succ_opt()
+                    .unwrap()
+                    .and_hms_opt(NIGHT_END, 0, 0)
+                    .unwrap();
+                overlap(shift, (start, end))
+            })
+            .fold(Duration::zero(), |total, d| total + d)
+    }
+}
+
+/// Повторяющийся цикл смен: `None` - выходной день графика.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShiftPattern {
+    cycle: Vec<Option<Shift>>,
+}
+
+impl ShiftPattern {
+    pub fn new(cycle: Vec<Option<Shift>>) -> Result<Self, ProductCalendarError> {
+        if cycle.iter().all(Option::is_none) {
+            return Err(ProductCalendarError::ScheduleError(
+                "в цикле графика нет ни одной смены".to_string(),
+            ));
+        }
+        Ok(Self { cycle })
+    }
+
+    /// График "`on` через `off`": 2/2, 1/3, 5/2.
+    pub fn on_off(on: usize, off: usize, shift: Shift) -> Result<Self, ProductCalendarError> {
+        Self::new(
+            std::iter::repeat_n(Some(shift), on)
+                .chain(std::iter::repeat_n(None, off))
+                .collect(),
+        )
+    }
+
+    pub fn cycle(&self) -> &[Option<Shift>] {
+        &self.cycle
+    }
+}
+
+/// День графика сменности: день производственного календаря и смена, начинающаяся в этот день.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShiftDay {
+    pub day: Day,
+    pub shift: Option<Shift>,
+}
+
+/// Итоги графика сменности за период.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ShiftSummary {
+    pub shifts: u32,
+    pub hours: f64,
+    pub night_hours: f64,
+    //Смены, затрагивающие нерабочие праздничные дни, и часы в эти дни (ст. 153 ТК РФ)
+    pub holiday_shifts: u32,
+    pub holiday_hours: f64,
+    /// Норма рабочего времени за период по производственному календарю.
+    pub norm_hours: f64,
+}
+
+impl ShiftSummary {
+    /// Отклонение от нормы: переработка при положительном значении, недоработка - при отрицательном.
+    pub fn deviation(&self) -> f64 {
+        self.hours - self.norm_hours
+    }
+}
+
+/// График сменности: цикл смен, отсчитываемый от даты `anchor` (первого дня цикла).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShiftSchedule {
+    pattern: ShiftPattern,
+    anchor: NaiveDate,
+}
+
+impl ShiftSchedule {
+    pub fn new(pattern: ShiftPattern, anchor: NaiveDate) -> Self {
+        Self { pattern, anchor }
+    }
+
+    pub fn pattern(&self) -> &ShiftPattern {
+        &self.pattern
+    }
+
+    pub fn anchor(&self) -> NaiveDate {
+        self.anchor
+    }
+
+    /// Смена, начинающаяся в день `date`, в том числе до даты отсчета.
+    pub fn shift_on(&self, date: NaiveDate) -> Option<Shift> {
+        let cycle = self.pattern.cycle.len() as i64;
+        let offset = (date - self.anchor).num_days().rem_euclid(cycle);
+        self.pattern.cycle[offset as usize]
+    }
+
+    /// График сотрудника с `start` по `end` включительно поверх производственного календаря.
+    pub fn roster(
+        &self,
+        pc: &ProductCalendar,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ShiftDay>, ProductCalendarError> {
+        Ok(pc
+            .period_slice(start, end)?
+            .into_iter()
+            .map(|day| ShiftDay {
+                shift: self.shift_on(day.get_date()),
+                day,
+            })
+            .collect())
+    }
+
+    /// Итоги графика с `start` по `end` включительно и их сравнение с нормой
+    /// рабочего времени для недели `week`. Смена относится к дню своего начала.
+    pub fn summary(
+        &self,
+        pc: &ProductCalendar,
+        start: NaiveDate,
+        end: NaiveDate,
+        week: WeekLength,
+    ) -> Result<ShiftSummary, ProductCalendarError> {
+        let mut summary = ShiftSummary {
+            norm_hours: pc.statistic_between(start, end)?.work_hours_norm(week),
+            ..Default::default()
+        };
+
+        for ShiftDay { day, shift } in self.roster(pc, start, end)? {
+            let Some(shift) = shift else {
+                continue;
+            };
+            summary.shifts += 1;
+            summary.hours += hours(shift.duration);
+            summary.night_hours += hours(shift.night_duration());
+
+            //Смена не длиннее суток и может перейти в праздник следующего дня
+            let bounds = shift.bounds(day.get_date());
+            let mut holiday = Duration::zero();
+            for date in day.get_date().iter_days().take(2) {
+                let midnight = date.and_time(NaiveTime::MIN);
+                if midnight >= bounds.1 {
+                    break;
+                }
+                let current = if date == day.get_date() {
+                    day.clone()
+                } else {
+                    pc.day_at(date)?
+                };
+                //Праздники по ст. 112 ТК РФ и региональные, а не перенесенные выходные
+                if current.is_named_holiday() {
+                    holiday += overlap(bounds, (midnight, midnight + Duration::days(1)));
+                }
+            }
+            if holiday > Duration::zero() {
+                summary.holiday_shifts += 1;
+                summary.holiday_hours += hours(holiday);
+            }
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::kind::DayKind;
+    use crate::pc::get_product_calendar;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn shift(hour: u32, hours: i64) -> Shift {
+        Shift::new(
+            NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+            Duration::hours(hours),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_shift_on() {
+        let pattern = ShiftPattern::on_off(2, 2, shift(8, 12)).unwrap();
+        let schedule = ShiftSchedule::new(pattern, date(2024, 1, 1));
+
+        assert!(schedule.shift_on(date(2024, 1, 2)).is_some());
+        assert!(schedule.shift_on(date(2024, 1, 3)).is_none());
+        assert!(schedule.shift_on(date(2024, 1, 5)).is_some());
+        //До даты отсчета цикл продолжается назад
+        assert!(schedule.shift_on(date(2023, 12, 31)).is_none());
+        assert!(schedule.shift_on(date(2023, 12, 29)).is_some());
+        assert!(ShiftPattern::new(vec![None, None]).is_err());
+    }
+
+    #[test]
+    fn test_night_duration() {
+        assert_eq!(shift(20, 12).night_duration(), Duration::hours(8));
+        assert_eq!(shift(8, 12).night_duration(), Duration::zero());
+        assert_eq!(shift(4, 4).night_duration(), Duration::hours(2));
+        //23:00 - 06:00 и 22:00 - 23:00 следующих суток
+        assert_eq!(shift(23, 24).night_duration(), Duration::hours(8));
+        assert_eq!(shift(2, 24).night_duration(), Duration::hours(8));
+    }
+
+    #[test]
+    fn test_summary() {
+        let pc = get_product_calendar(Some(2024)).unwrap();
+        //День - ночь - отсыпной - выходной
+        let pattern =
+            ShiftPattern::new(vec![Some(shift(8, 12)), Some(shift(20, 12)), None, None]).unwrap();
+        let schedule = ShiftSchedule::new(pattern, date(2024, 4, 30));
+
+        let may = schedule
+            .summary(&pc, date(2024, 5, 1), date(2024, 5, 31), WeekLength::Forty)
+            .unwrap();
+        assert_eq!(may.shifts, 15);
+        assert_eq!(may.hours, 180.0);
+        assert_eq!(may.night_hours, 8.0 * 8.0);
+        assert_eq!(may.norm_hours, 159.0);
+        assert_eq!(may.deviation(), 21.0);
+        //Ночные смены 1 и 9 мая: праздничные часы только до полуночи,
+        //перенесенный выходной 10 мая праздником не считается
+        assert_eq!(may.holiday_shifts, 2);
+        assert_eq!(may.holiday_hours, 4.0 + 4.0);
+
+        let roster = schedule
+            .roster(&pc, date(2024, 5, 1), date(2024, 5, 4))
+            .unwrap();
+        assert_eq!(roster.len(), 4);
+        assert_eq!(roster[0].shift, Some(shift(20, 12)));
+        assert_eq!(roster[0].day.get_kind(), DayKind::Holiday);
+    }
+
+    #[test]
+    fn test_weekend_holidays() {
+        //Праздники, совпавшие с выходными, хранятся как выходные с названием
+        let schedule = ShiftSchedule::new(
+            ShiftPattern::on_off(1, 0, shift(8, 12)).unwrap(),
+            date(2024, 1, 1),
+        );
+        for (year, month, day) in [(2024, 1, 7), (2026, 5, 9)] {
+            let pc = get_product_calendar(Some(year)).unwrap();
+            let day = date(year as i32, month, day);
+            let summary = schedule.summary(&pc, day, day, WeekLength::Forty).unwrap();
+            assert_eq!(summary.holiday_shifts, 1);
+            assert_eq!(summary.holiday_hours, 12.0);
+        }
+    }
+}
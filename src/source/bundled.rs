@@ -0,0 +1,70 @@
+use super::CalendarSource;
+use crate::day::Day;
+use crate::errors::ProductCalendarError;
+use std::error::Error;
+
+/// Версия встроенных данных, меняется при каждом обновлении файлов в `data/`
+pub const BUNDLED_DATA_VERSION: &str = "2025.2";
+
+const BUNDLED: &[(u16, &str)] = &[
+    (2022, include_str!("../../data/2022.json")),
+    (2023, include_str!("../../data/2023.json")),
+    (2024, include_str!("../../data/2024.json")),
+    (2025, include_str!("../../data/2025.json")),
+    (2026, include_str!("../../data/2026.json")),
+];
+
+/// Встроенные в библиотеку данные опубликованных производственных календарей.
+/// Не требует доступа в сеть.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BundledSource;
+
+impl BundledSource {
+    pub fn years() -> Vec<u16> {
+        BUNDLED.iter().map(|(year, _)| *year).collect()
+    }
+}
+
+impl CalendarSource for BundledSource {
+    fn name(&self) -> String {
+        format!("bundled:{}", BUNDLED_DATA_VERSION)
+    }
+
+    fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn Error>> {
+        let (_, data) = BUNDLED
+            .iter()
+            .find(|(y, _)| *y == year)
+            .ok_or_else(|| ProductCalendarError::InvalidYear(year.to_string()))?;
+        Ok(serde_json::from_str(data)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pc::get_product_calendar_from;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_bundled_years_match_official_totals() {
+        //(год, рабочих дней, часов при 40-часовой неделе)
+        let expected = [
+            (2022, 247, 1973),
+            (2023, 247, 1973),
+            (2024, 248, 1979),
+            (2025, 247, 1972),
+            (2026, 247, 1972),
+        ];
+        for (year, work_days, work_hours) in expected {
+            let pc = get_product_calendar_from(Some(year), Arc::new(BundledSource)).unwrap();
+            let statistic = pc.statistic();
+            assert_eq!(statistic.work_days + statistic.preholidays, work_days);
+            assert_eq!(statistic.work_hours(), work_hours);
+        }
+    }
+
+    #[test]
+    fn test_missing_year() {
+        assert!(BundledSource.fetch(1999).is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use super::CalendarSource;
+use crate::day::Day;
+use crate::norms::Norms;
+use crate::parser::{ParsedCalendar, ProductCalendarParser, URL};
+use std::error::Error;
+
+/// Данные с consultant.ru (или его зеркала с той же разметкой).
+#[derive(Debug, Clone)]
+pub struct ConsultantSource {
+    base_url: String,
+}
+
+impl ConsultantSource {
+    pub fn with_url(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.to_owned(),
+        }
+    }
+}
+
+impl Default for ConsultantSource {
+    fn default() -> Self {
+        Self::with_url(URL)
+    }
+}
+
+impl CalendarSource for ConsultantSource {
+    fn name(&self) -> String {
+        format!("consultant:{}", self.base_url)
+    }
+
+    fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn Error>> {
+        ProductCalendarParser::with_url(year, &self.base_url).parse_calendar()
+    }
+
+    //Дни и нормы с одной страницы, без повторного запроса
+    fn fetch_with_norms(&self, year: u16) -> Result<(Vec<Day>, Option<Norms>), Box<dyn Error>> {
+        let ParsedCalendar { days, norms } =
+            ProductCalendarParser::with_url(year, &self.base_url).parse()?;
+        Ok((days, Some(norms)))
+    }
+
+    fn fetch_norms(&self, year: u16) -> Result<Option<Norms>, Box<dyn Error>> {
+        let norms = ProductCalendarParser::with_url(year, &self.base_url).parse_norms()?;
+        Ok(Some(norms))
+    }
+}
@@ -0,0 +1,58 @@
+use super::CalendarSource;
+use crate::day::Day;
+use crate::norms::Norms;
+use std::error::Error;
+use std::sync::Arc;
+
+/// Опрашивает источники по порядку и возвращает данные первого успешного.
+#[derive(Debug, Clone)]
+pub struct FallbackSource {
+    sources: Vec<Arc<dyn CalendarSource>>,
+}
+
+impl FallbackSource {
+    pub fn new(sources: Vec<Arc<dyn CalendarSource>>) -> Self {
+        Self { sources }
+    }
+}
+
+impl CalendarSource for FallbackSource {
+    fn name(&self) -> String {
+        let names: Vec<String> = self.sources.iter().map(|s| s.name()).collect();
+        format!("fallback[{}]", names.join(","))
+    }
+
+    fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn Error>> {
+        let mut last_err: Box<dyn Error> = "Не задано ни одного источника".into();
+        for source in &self.sources {
+            match source.fetch(year) {
+                Ok(days) => return Ok(days),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+
+    fn fetch_with_norms(&self, year: u16) -> Result<(Vec<Day>, Option<Norms>), Box<dyn Error>> {
+        let mut last_err: Box<dyn Error> = "Не задано ни одного источника".into();
+        for source in &self.sources {
+            match source.fetch_with_norms(year) {
+                Ok(data) => return Ok(data),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+
+    fn fetch_norms(&self, year: u16) -> Result<Option<Norms>, Box<dyn Error>> {
+        let mut last_err = None;
+        for source in &self.sources {
+            match source.fetch_norms(year) {
+                Ok(Some(norms)) => return Ok(Some(norms)),
+                Ok(None) => {}
+                Err(e) => last_err = Some(e),
+            }
+        }
+        last_err.map_or(Ok(None), Err)
+    }
+}
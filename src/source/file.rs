@@ -0,0 +1,64 @@
+use super::CalendarSource;
+use crate::day::Day;
+use crate::errors::ProductCalendarError;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// Локальный каталог с файлами `<год>.json`,
+/// каждый файл - сериализованный список `Day`.
+#[derive(Debug, Clone)]
+pub struct JsonFileSource {
+    dir: PathBuf,
+}
+
+impl JsonFileSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn path(&self, year: u16) -> PathBuf {
+        self.dir.join(format!("{}.json", year))
+    }
+}
+
+impl CalendarSource for JsonFileSource {
+    fn name(&self) -> String {
+        format!("file:{}", self.dir.display())
+    }
+
+    fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn Error>> {
+        let path = self.path(year);
+        if !path.exists() {
+            return Err(Box::new(ProductCalendarError::InvalidYear(
+                year.to_string(),
+            )));
+        }
+        let days = serde_json::from_str(&fs::read_to_string(path)?)?;
+        Ok(days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day::kind::DayKind;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_fetch_from_dir() {
+        let dir = std::env::temp_dir().join("product_calendar_file_source");
+        fs::create_dir_all(&dir).unwrap();
+        let mut day = Day::new(NaiveDate::from_ymd_opt(2030, 1, 1).unwrap());
+        day.set_kind(DayKind::Holiday);
+        fs::write(
+            dir.join("2030.json"),
+            serde_json::to_string(&vec![day.clone()]).unwrap(),
+        )
+        .unwrap();
+
+        let source = JsonFileSource::new(&dir);
+        assert_eq!(source.fetch(2030).unwrap(), vec![day]);
+        assert!(source.fetch(2031).is_err());
+    }
+}
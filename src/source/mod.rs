@@ -0,0 +1,101 @@
+#[cfg(feature = "bundled")]
+pub mod bundled;
+pub mod consultant;
+pub mod fallback;
+pub mod file;
+pub mod predicted;
+
+#[cfg(feature = "bundled")]
+pub use self::bundled::BundledSource;
+pub use self::consultant::ConsultantSource;
+pub use self::fallback::FallbackSource;
+pub use self::file::JsonFileSource;
+pub use self::predicted::PredictedSource;
+
+use crate::day::Day;
+use crate::norms::Norms;
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+lazy_static! {
+    static ref DEFAULT_SOURCE: Arc<dyn CalendarSource> = build_default_source();
+}
+
+//Встроенные данные, а сеть - только для отсутствующих в них лет.
+//Для еще не опубликованных лет - предварительный календарь по правилам ТК РФ
+#[cfg(feature = "bundled")]
+fn build_default_source() -> Arc<dyn CalendarSource> {
+    Arc::new(FallbackSource::new(vec![
+        Arc::new(BundledSource),
+        Arc::new(ConsultantSource::default()),
+        Arc::new(PredictedSource),
+    ]))
+}
+
+#[cfg(not(feature = "bundled"))]
+fn build_default_source() -> Arc<dyn CalendarSource> {
+    Arc::new(FallbackSource::new(vec![
+        Arc::new(ConsultantSource::default()),
+        Arc::new(PredictedSource),
+    ]))
+}
+
+/// Источник данных производственного календаря.
+///
+/// Возвращает дни года, отличающиеся от обычной пятидневки
+/// (праздники, предпраздничные и перенесенные рабочие дни).
+/// Остальные дни достраиваются в `ProductCalendar::new`.
+pub trait CalendarSource: Debug + Send + Sync {
+    /// Уникальное имя источника, используется в ключе кэша.
+    fn name(&self) -> String;
+
+    /// Возвращает дни производственного календаря за год.
+    fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn Error>>;
+
+    /// Дни производственного календаря за год и официальные нормы,
+    /// если источник получает их тем же запросом.
+    fn fetch_with_norms(&self, year: u16) -> Result<(Vec<Day>, Option<Norms>), Box<dyn Error>> {
+        Ok((self.fetch(year)?, None))
+    }
+
+    /// Официальные нормы рабочего времени за год, если источник их публикует.
+    /// Вызывается, только если нормы не были получены вместе с днями.
+    fn fetch_norms(&self, _year: u16) -> Result<Option<Norms>, Box<dyn Error>> {
+        Ok(None)
+    }
+
+    /// Можно ли сохранять данные источника в кэш на диске.
+    fn persistent_cache(&self) -> bool {
+        true
+    }
+
+    /// Можно ли хранить данные источника в кэше процесса.
+    /// Некэшируемый источник опрашивается при каждой загрузке года.
+    fn cacheable(&self) -> bool {
+        true
+    }
+}
+
+/// Источник по умолчанию: встроенные данные с переходом на consultant.ru
+/// (без feature `bundled` - только consultant.ru), а затем на предварительный календарь
+pub fn default_source() -> Arc<dyn CalendarSource> {
+    DEFAULT_SOURCE.clone()
+}
+
+/// Создает источник по строковому описанию:
+/// `consultant` - только consultant.ru, `bundled` - только встроенные данные,
+/// `predicted` - только предварительный календарь по правилам ТК РФ,
+/// адрес `http(s)://...` - зеркало consultant.ru, иначе - каталог с json файлами.
+pub fn source_from_spec(spec: &str) -> Arc<dyn CalendarSource> {
+    match spec {
+        "consultant" => Arc::new(ConsultantSource::default()),
+        "predicted" => Arc::new(PredictedSource),
+        #[cfg(feature = "bundled")]
+        "bundled" => Arc::new(BundledSource),
+        _ if spec.starts_with("http://") || spec.starts_with("https://") => {
+            Arc::new(ConsultantSource::with_url(spec))
+        }
+        _ => Arc::new(JsonFileSource::new(spec)),
+    }
+}
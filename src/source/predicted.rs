@@ -0,0 +1,136 @@
+use super::CalendarSource;
+use crate::day::{holiday::PUBLIC_HOLIDAYS, kind::DayKind, Day};
+use crate::errors::ProductCalendarError;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+//Первый год действия текущей редакции ст. 112 ТК РФ
+const FIRST_RULES_YEAR: u16 = 2013;
+
+/// Предварительный календарь, рассчитанный по правилам ст. 112 и 95 ТК РФ:
+/// * праздник, совпавший с выходным, переносится на следующий рабочий день
+///   (кроме новогодних праздников - их переносит постановление правительства);
+/// * рабочий день перед праздником сокращается на час.
+///
+/// Все дни помечаются как предсказанные (`Day::is_predicted`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PredictedSource;
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+fn public_holidays(year: i32) -> BTreeSet<NaiveDate> {
+    PUBLIC_HOLIDAYS
+        .iter()
+        .filter_map(|&(month, day, _)| NaiveDate::from_ymd_opt(year, month, day))
+        .collect()
+}
+
+impl CalendarSource for PredictedSource {
+    fn name(&self) -> String {
+        "predicted".to_string()
+    }
+
+    fn fetch(&self, year: u16) -> Result<Vec<Day>, Box<dyn Error>> {
+        if year < FIRST_RULES_YEAR {
+            return Err(Box::new(ProductCalendarError::InvalidYear(
+                year.to_string(),
+            )));
+        }
+        let year = year as i32;
+        let holidays = public_holidays(year);
+
+        let mut days_off: BTreeSet<NaiveDate> = holidays
+            .iter()
+            .copied()
+            .filter(|d| !is_weekend(*d))
+            .collect();
+        //Перенос выходного: дата праздника -> дата нового выходного
+        let mut transfers = BTreeMap::new();
+        for holiday in holidays
+            .iter()
+            .filter(|d| is_weekend(**d) && d.month() != 1)
+        {
+            let mut transfer = *holiday + Duration::days(1);
+            while is_weekend(transfer)
+                || holidays.contains(&transfer)
+                || days_off.contains(&transfer)
+            {
+                transfer += Duration::days(1);
+            }
+            days_off.insert(transfer);
+            transfers.insert(*holiday, transfer);
+        }
+
+        //Рабочие дни, непосредственно предшествующие праздникам, в том числе 1 января следующего года
+        let preholidays: BTreeSet<NaiveDate> = holidays
+            .iter()
+            .chain(&NaiveDate::from_ymd_opt(year + 1, 1, 1))
+            .filter_map(|d| d.pred_opt())
+            .filter(|d| d.year() == year && !is_weekend(*d) && !days_off.contains(d))
+            .filter(|d| !holidays.contains(d))
+            .collect();
+
+        let start = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+        let calendar = start
+            .iter_days()
+            .take_while(|d| d.year() == year)
+            .map(|date| {
+                let mut day = Day::new(date);
+                if days_off.contains(&date) {
+                    day.set_kind(DayKind::Holiday);
+                } else if preholidays.contains(&date) {
+                    day.set_kind(DayKind::Preholiday);
+                }
+                if let Some(to) = transfers.get(&date) {
+                    day.set_transferred_to(Some(*to));
+                }
+                if let Some((from, _)) = transfers.iter().find(|(_, to)| **to == date) {
+                    day.set_transferred_from(Some(*from));
+                }
+                day.set_predicted(true);
+                day
+            })
+            .collect();
+        Ok(calendar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(days: &[Day], month: u32, day: u32) -> DayKind {
+        let date = NaiveDate::from_ymd_opt(days[0].get_year(), month, day).unwrap();
+        days.iter()
+            .find(|d| d.get_date() == date)
+            .unwrap()
+            .get_kind()
+    }
+
+    #[test]
+    fn test_predicted_2023() {
+        let days = PredictedSource.fetch(2023).unwrap();
+        assert_eq!(days.len(), 365);
+        assert!(days.iter().all(Day::is_predicted));
+        //4 ноября - суббота, выходной переносится на понедельник
+        assert_eq!(kind(&days, 11, 6), DayKind::Holiday);
+        assert_eq!(
+            days[309].get_transferred_from(),
+            NaiveDate::from_ymd_opt(2023, 11, 4)
+        );
+        assert_eq!(kind(&days, 11, 3), DayKind::Preholiday);
+        //Новогодние праздники в выходные не переносятся автоматически
+        assert_eq!(kind(&days, 1, 7), DayKind::Weekend);
+        assert_eq!(kind(&days, 1, 9), DayKind::Work);
+        assert_eq!(kind(&days, 2, 22), DayKind::Preholiday);
+        assert_eq!(kind(&days, 12, 29), DayKind::Work);
+    }
+
+    #[test]
+    fn test_rules_not_applicable() {
+        assert!(PredictedSource.fetch(2000).is_err());
+    }
+}
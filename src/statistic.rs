@@ -1,6 +1,37 @@
+use crate::errors::ProductCalendarError;
 use std::collections::HashMap;
 use std::fmt;
 
+/// Продолжительность рабочей недели (ст. 91, 92 ТК РФ).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WeekLength {
+    #[default]
+    Forty,
+    //Сокращенная неделя для медицинских и педагогических работников
+    ThirtySix,
+    //Сокращенная неделя для несовершеннолетних и инвалидов
+    TwentyFour,
+}
+
+impl WeekLength {
+    pub fn hours(&self) -> u16 {
+        match self {
+            WeekLength::Forty => 40,
+            WeekLength::ThirtySix => 36,
+            WeekLength::TwentyFour => 24,
+        }
+    }
+
+    pub fn from_hours(hours: u16) -> Result<Self, ProductCalendarError> {
+        match hours {
+            40 => Ok(WeekLength::Forty),
+            36 => Ok(WeekLength::ThirtySix),
+            24 => Ok(WeekLength::TwentyFour),
+            _ => Err(ProductCalendarError::InvalidWeekLength(hours)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, Eq)]
 pub struct Statistic {
     pub holidays: u16,
@@ -20,6 +51,15 @@ impl Statistic {
         self.work_days * 8 + self.preholidays * 7
     }
 
+    //Норма по приказу Минздравсоцразвития № 588н:
+    //недельная норма / 5 на каждый рабочий день минус час за каждый предпраздничный
+    pub fn work_hours_norm(&self, week: WeekLength) -> f64 {
+        let days = (self.work_days + self.preholidays) as u32;
+        //Считаем в десятых долях часа, чтобы не копить ошибку округления
+        let tenths = days * week.hours() as u32 * 2 - self.preholidays as u32 * 10;
+        tenths as f64 / 10.0
+    }
+
     pub fn as_map(&self) -> HashMap<String, u16> {
         let mut day_map = HashMap::with_capacity(4);
         day_map.insert("holidays".to_owned(), self.holidays);
@@ -48,3 +88,21 @@ impl fmt::Display for Statistic {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_work_hours_norm_2024() {
+        let statistic = Statistic {
+            holidays: 17,
+            work_days: 243,
+            weekends: 101,
+            preholidays: 5,
+        };
+        assert_eq!(statistic.work_hours_norm(WeekLength::Forty), 1979.0);
+        assert_eq!(statistic.work_hours_norm(WeekLength::ThirtySix), 1780.6);
+        assert_eq!(statistic.work_hours_norm(WeekLength::TwentyFour), 1185.4);
+    }
+}